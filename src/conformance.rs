@@ -0,0 +1,15 @@
+use crate::conformance::runner::TestRunner;
+use crate::lexing::scanner::CommentStyle;
+use crate::util::error_handling::InterpreterError;
+
+pub mod expectation;
+pub mod runner;
+
+mod tests;
+
+pub fn run_test_suite(directory: &str, comment_style: CommentStyle) -> Result<String, InterpreterError> {
+    let runner = TestRunner::for_current_executable(comment_style)?;
+    let report = runner.run(directory)?;
+
+    Ok(report)
+}
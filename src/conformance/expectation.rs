@@ -0,0 +1,142 @@
+use exitcode::ExitCode;
+
+const EXPECT_OUTPUT: &str = "// expect: ";
+const EXPECT_RUNTIME_ERROR: &str = "// expect runtime error: ";
+const EXPECT_ERROR: &str = "// Error";
+const EXPECT_ERROR_WITH_LINE: &str = "// [line ";
+const EXPECT_JAVA_ERROR_WITH_LINE: &str = "// [java line ";
+
+const COMPILE_ERROR_EXIT_CODE: ExitCode = 65;
+const RUNTIME_ERROR_EXIT_CODE: ExitCode = 70;
+
+//** OUTCOME ***********************************************************************************************************
+
+/// What actually happened when a test file was run.
+pub struct Outcome {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: ExitCode
+}
+
+//** EXPECTED RUNTIME ERROR *******************************************************************************************
+
+/// A runtime error is expected on the line that holds its annotation.
+#[derive(Debug, PartialEq)]
+pub struct ExpectedRuntimeError {
+    pub message: String,
+    pub line: usize
+}
+
+//** EXPECTATION *******************************************************************************************************
+
+/// The behaviour a test file describes through its comments, using the same annotations as
+/// the test suite that accompanies Crafting Interpreters:
+///
+/// - `// expect: value` is a line the program should print.
+/// - `// expect runtime error: message` is the error the program should stop with, on the
+///   line the annotation is written on.
+/// - `// [line N] Error at 'x': message` or `// Error at 'x': message` is an error that
+///   should be reported before the program runs.  The second form refers to its own line.
+///
+/// Annotations marked `[c line N]` only apply to the C implementation and are ignored.
+#[derive(Debug, Default)]
+pub struct Expectation {
+    pub output: Vec<String>,
+    pub compile_errors: Vec<String>,
+    pub runtime_error: Option<ExpectedRuntimeError>
+}
+
+impl Expectation {
+    pub fn from_source(source: &str) -> Self {
+        let mut expectation = Expectation::default();
+
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+
+            if let Some(position) = line.find(EXPECT_OUTPUT) {
+                expectation.output.push(line[position + EXPECT_OUTPUT.len()..].to_string());
+            } else if let Some(position) = line.find(EXPECT_RUNTIME_ERROR) {
+                expectation.runtime_error = Some(ExpectedRuntimeError {
+                    message: line[position + EXPECT_RUNTIME_ERROR.len()..].to_string(),
+                    line: line_number
+                });
+            } else if let Some(position) = line.find(EXPECT_ERROR_WITH_LINE) {
+                expectation.compile_errors.push(line[position + 3..].to_string());
+            } else if let Some(position) = line.find(EXPECT_JAVA_ERROR_WITH_LINE) {
+                expectation.compile_errors.push(format!("[line {}", &line[position + EXPECT_JAVA_ERROR_WITH_LINE.len()..]));
+            } else if let Some(position) = line.find(EXPECT_ERROR) {
+                expectation.compile_errors.push(format!("[line {}] {}", line_number, &line[position + 3..]));
+            }
+        }
+
+        expectation
+    }
+
+    pub fn exit_code(&self) -> ExitCode {
+        if !self.compile_errors.is_empty() {
+            COMPILE_ERROR_EXIT_CODE
+        } else if self.runtime_error.is_some() {
+            RUNTIME_ERROR_EXIT_CODE
+        } else {
+            exitcode::OK
+        }
+    }
+
+    /// Compares the outcome against the expectation and describes every difference found.  An
+    /// empty list means the test passed.
+    ///
+    /// Like the reference implementation, a runtime error is reported as its message followed
+    /// by `[line N]` on the next line.  Both are compared.
+    pub fn check(&self, outcome: &Outcome) -> Vec<String> {
+        let mut failures = Vec::new();
+        let actual_output: Vec<&str> = outcome.stdout.lines().collect();
+
+        for (index, expected) in self.output.iter().enumerate() {
+            match actual_output.get(index) {
+                Some(actual) if actual == expected => {},
+                Some(actual) => failures.push(format!("Expected output '{}' on line {} but got '{}'.", expected, index + 1, actual)),
+                None => failures.push(format!("Missing expected output '{}' on line {}.", expected, index + 1))
+            }
+        }
+
+        for extra in actual_output.iter().skip(self.output.len()) {
+            failures.push(format!("Got unexpected output '{}'.", extra));
+        }
+
+        let actual_errors: Vec<&str> = outcome.stderr.lines().collect();
+
+        if let Some(expected) = &self.runtime_error {
+            let expected_line = format!("[line {}]", expected.line);
+
+            match actual_errors.first() {
+                Some(actual) if *actual == expected.message => {},
+                Some(actual) => failures.push(format!("Expected runtime error '{}' but got '{}'.", expected.message, actual)),
+                None => failures.push(format!("Expected runtime error '{}' but got none.", expected.message))
+            }
+
+            match actual_errors.get(1) {
+                Some(actual) if *actual == expected_line => {},
+                Some(actual) => failures.push(format!("Expected runtime error on '{}' but got '{}'.", expected_line, actual)),
+                None => failures.push(format!("Expected runtime error on '{}' but no line was reported.", expected_line))
+            }
+        } else {
+            for expected in &self.compile_errors {
+                if !actual_errors.contains(&expected.as_str()) {
+                    failures.push(format!("Missing expected error '{}'.", expected));
+                }
+            }
+
+            for actual in &actual_errors {
+                if !self.compile_errors.iter().any(|expected| expected == actual) {
+                    failures.push(format!("Got unexpected error '{}'.", actual));
+                }
+            }
+        }
+
+        if outcome.exit_code != self.exit_code() {
+            failures.push(format!("Expected exit code {} but got {}.", self.exit_code(), outcome.exit_code));
+        }
+
+        failures
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt;
+use std::fs;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use exitcode::ExitCode;
+use crate::conformance::expectation::{Expectation, Outcome};
+use crate::lexing::scanner::CommentStyle;
+use crate::util::error_handling::ExitCodeProvider;
+
+const TEST_FILE_EXTENSION: &str = "lox";
+
+//** CONFORMANCE ERRORS ************************************************************************************************
+
+#[derive(Debug)]
+pub enum ConformanceError {
+    ExecutableNotFound,
+    UnreadableDirectory { directory: String },
+    TestsFailed { report: String, failed: usize, total: usize }
+}
+
+impl fmt::Display for ConformanceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConformanceError::ExecutableNotFound =>
+                write!(f, "Unable to locate the interpreter executable."),
+
+            ConformanceError::UnreadableDirectory { directory } =>
+                write!(f, "Unable to read test directory: {}", directory),
+
+            ConformanceError::TestsFailed { report: _, failed, total } =>
+                write!(f, "{} of {} tests failed.", failed, total)
+        }
+    }
+}
+
+impl ExitCodeProvider for ConformanceError {
+    fn get_output(&self) -> Option<String> {
+        match self {
+            ConformanceError::ExecutableNotFound => None,
+            ConformanceError::UnreadableDirectory { .. } => None,
+            ConformanceError::TestsFailed { report, .. } => Some(report.to_string())
+        }
+    }
+
+    fn get_error_details(&self) -> Option<String> {
+        Some(format!("{}", self))
+    }
+
+    fn get_exit_code(&self) -> ExitCode {
+        match self {
+            ConformanceError::ExecutableNotFound => exitcode::OSFILE,
+            ConformanceError::UnreadableDirectory { .. } => exitcode::IOERR,
+            ConformanceError::TestsFailed { .. } => exitcode::SOFTWARE
+        }
+    }
+}
+
+//** TEST RUNNER *******************************************************************************************************
+
+/// Runs every `.lox` file below a directory and compares what happened with the expectations
+/// written in the file's comments.  Each file is run by a separate copy of this executable
+/// using the `run` command, so the output, errors and exit code are exactly what a user would
+/// see.  The comment style the suite was started with is passed on to every file.
+pub struct TestRunner {
+    executable: PathBuf,
+    comment_style: CommentStyle
+}
+
+impl TestRunner {
+    pub fn new(executable: PathBuf, comment_style: CommentStyle) -> Self {
+        TestRunner { executable, comment_style }
+    }
+
+    pub fn for_current_executable(comment_style: CommentStyle) -> Result<Self, ConformanceError> {
+        match env::current_exe() {
+            Ok(executable) => Ok(TestRunner::new(executable, comment_style)),
+            Err(_) => Err(ConformanceError::ExecutableNotFound)
+        }
+    }
+
+    fn collect_test_files(directory: &Path, files: &mut Vec<PathBuf>) -> Result<(), ConformanceError> {
+        let entries = fs::read_dir(directory).map_err(|_| ConformanceError::UnreadableDirectory {
+            directory: directory.display().to_string()
+        })?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.is_dir() {
+                Self::collect_test_files(&path, files)?;
+            } else if path.extension().is_some_and(|extension| extension == TEST_FILE_EXTENSION) {
+                files.push(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn run_test_file(&self, path: &Path) -> Vec<String> {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(_) => return vec!["Unable to read test file.".to_string()]
+        };
+
+        let output = match Command::new(&self.executable).arg("run").arg(path).arg(self.comment_style.to_flag()).output() {
+            Ok(output) => output,
+            Err(_) => return vec!["Unable to start the interpreter.".to_string()]
+        };
+
+        let outcome = Outcome {
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            exit_code: output.status.code().unwrap_or(-1)
+        };
+
+        Expectation::from_source(&source).check(&outcome)
+    }
+
+    /// Files are run in path order so that reports from different runs line up.
+    pub fn run(&self, directory: &str) -> Result<String, ConformanceError> {
+        let mut files = Vec::new();
+        Self::collect_test_files(Path::new(directory), &mut files)?;
+        files.sort();
+
+        let mut report = Vec::new();
+        let mut failed = 0;
+
+        for file in &files {
+            let failures = self.run_test_file(file);
+
+            if failures.is_empty() {
+                report.push(format!("PASS {}", file.display()));
+            } else {
+                failed += 1;
+                report.push(format!("FAIL {}", file.display()));
+                report.extend(failures.iter().map(|failure| format!("    {}", failure)));
+            }
+        }
+
+        report.push(format!("Passed {} of {} tests.", files.len() - failed, files.len()));
+
+        match failed {
+            0 => Ok(report.join("\n")),
+            _ => Err(ConformanceError::TestsFailed { report: report.join("\n"), failed, total: files.len() })
+        }
+    }
+}
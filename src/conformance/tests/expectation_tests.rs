@@ -0,0 +1,74 @@
+use crate::conformance::expectation::{Expectation, ExpectedRuntimeError, Outcome};
+
+#[test]
+fn should_collect_expected_output_in_order() {
+    let expectation = Expectation::from_source("print 1; // expect: 1\nprint \"a\"; // expect: a\n");
+
+    assert_eq!(expectation.output, vec!["1", "a"]);
+    assert_eq!(expectation.exit_code(), 0);
+}
+
+#[test]
+fn should_collect_expected_runtime_error() {
+    let expectation = Expectation::from_source("\n-\"a\"; // expect runtime error: Operand must be a number.");
+
+    assert_eq!(expectation.runtime_error, Some(ExpectedRuntimeError { message: "Operand must be a number.".to_string(), line: 2 }));
+    assert_eq!(expectation.exit_code(), 70);
+}
+
+#[test]
+fn should_collect_expected_compile_errors_with_and_without_line_numbers() {
+    let source = "\n// [line 5] Error at 'a': Expect ';'.\nreturn; // Error at 'return': Can't return from top-level code.\n// [java line 7] Error at end: Expect '}'.\n// [c line 7] Error at end: Ignored.";
+    let expectation = Expectation::from_source(source);
+
+    assert_eq!(expectation.compile_errors, vec![
+        "[line 5] Error at 'a': Expect ';'.",
+        "[line 3] Error at 'return': Can't return from top-level code.",
+        "[line 7] Error at end: Expect '}'."
+    ]);
+    assert_eq!(expectation.exit_code(), 65);
+}
+
+#[test]
+fn should_pass_when_outcome_matches() {
+    let expectation = Expectation::from_source("print 1; // expect: 1");
+    let outcome = Outcome { stdout: "1\n".to_string(), stderr: String::new(), exit_code: 0 };
+
+    assert!(expectation.check(&outcome).is_empty());
+}
+
+#[test]
+fn should_report_every_deviation() {
+    let expectation = Expectation::from_source("if (0) print 1; // expect: 1");
+    let outcome = Outcome { stdout: String::new(), stderr: "Oops.".to_string(), exit_code: 70 };
+
+    assert_eq!(expectation.check(&outcome), vec![
+        "Missing expected output '1' on line 1.",
+        "Got unexpected error 'Oops.'.",
+        "Expected exit code 0 but got 70."
+    ]);
+}
+
+#[test]
+fn should_compare_message_and_line_of_a_runtime_error() {
+    let expectation = Expectation::from_source("nil(); // expect runtime error: Can only call functions and classes.");
+    let outcome = Outcome {
+        stdout: String::new(),
+        stderr: "Can only call functions and classes.\n[line 1]".to_string(),
+        exit_code: 70
+    };
+
+    assert!(expectation.check(&outcome).is_empty());
+}
+
+#[test]
+fn should_report_runtime_error_on_wrong_line() {
+    let expectation = Expectation::from_source("\nnil(); // expect runtime error: Can only call functions and classes.");
+    let outcome = Outcome {
+        stdout: String::new(),
+        stderr: "Can only call functions and classes.\n[line 1]".to_string(),
+        exit_code: 70
+    };
+
+    assert_eq!(expectation.check(&outcome), vec!["Expected runtime error on '[line 2]' but got '[line 1]'."]);
+}
@@ -1,21 +1,28 @@
 use std::fs;
 use crate::interpreting::evaluator::Evaluator;
 use crate::interpreting::interpreter::Interpreter;
-use crate::lexing::scanner::Scanner;
+use crate::lexing::scanner::{CommentStyle, Scanner};
 use crate::parsing::parser::Parser;
+use crate::resolving::resolver::Resolver;
 use crate::util::error_handling::InterpreterError;
 
+pub mod callable;
+pub mod class;
+pub mod environment;
 pub mod evaluator;
+pub mod list;
+pub mod map;
+pub mod native;
 mod tests;
 mod interpreter;
 
-pub fn evaluate_ast(filename: &str) -> Result<String, InterpreterError> {
+pub fn evaluate_ast(filename: &str, comment_style: CommentStyle) -> Result<String, InterpreterError> {
     let file_contents = fs::read_to_string(filename).unwrap_or_else(|_| {
         eprintln!("Failed to read file {}:  Defaulting to an empty string", filename);
         String::new()
     });
 
-    let mut scanner = Scanner::new(file_contents);
+    let mut scanner = Scanner::new(file_contents).with_comment_style(comment_style);
     let tokens = scanner.scan_tokens()?;
 
     let parser = Parser::new(tokens);
@@ -27,18 +34,21 @@ pub fn evaluate_ast(filename: &str) -> Result<String, InterpreterError> {
     Ok(format!("{}", result))
 }
 
-pub fn interpret_program(filename: &str) -> Result<String, InterpreterError> {
+pub fn interpret_program(filename: &str, comment_style: CommentStyle) -> Result<String, InterpreterError> {
     let file_contents = fs::read_to_string(filename).unwrap_or_else(|_| {
         eprintln!("Failed to read file {}:  Defaulting to an empty string", filename);
         String::new()
     });
 
-    let mut scanner = Scanner::new(file_contents);
+    let mut scanner = Scanner::new(file_contents).with_comment_style(comment_style);
     let tokens = scanner.scan_tokens()?;
 
     let parser = Parser::new(tokens);
     let statements = parser.parse()?;
 
+    let mut resolver = Resolver::new();
+    resolver.resolve(&statements)?;
+
     let interpreter = Interpreter::new(statements, Evaluator::new(None));
     let result = interpreter.interpret()?;
 
@@ -0,0 +1,84 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use crate::interpreting::class::LoxInstance;
+use crate::interpreting::environment::Environment;
+use crate::interpreting::evaluator::{EvaluationError, Evaluator, EvaluatorResult};
+use crate::interpreting::interpreter::{ExecutionFlow, Interpreter};
+use crate::parsing::statement::FunctionDeclaration;
+
+//** CALLABLE **********************************************************************************************************
+
+/// Anything that can appear on the left-hand side of a call expression.  The evaluator checks
+/// the number of arguments against `arity` before `call` is invoked, so implementations can
+/// assume they received exactly the number of arguments they asked for.  The line of the call
+/// is passed along so that runtime errors raised by the callee can point at the call site.
+pub trait Callable {
+    fn arity(&self) -> usize;
+    fn call(&self, evaluator: &Evaluator, arguments: Vec<EvaluatorResult>, line: u16) -> Result<EvaluatorResult, EvaluationError>;
+}
+
+//** LOX FUNCTION ******************************************************************************************************
+
+/// A function declared in a Lox program.  The closure is the environment that was active when
+/// the declaration was executed.  Holding a reference to it keeps that scope alive after the
+/// call that created it has returned, which is what lets a nested function keep reading and
+/// updating the local variables of the function that declared it.
+///
+/// Methods are stored on the class as unbound functions.  When one is accessed through an
+/// instance it is bound, which wraps the closure in one more environment that defines `this`.
+pub struct LoxFunction {
+    declaration: Rc<FunctionDeclaration>,
+    closure: Rc<RefCell<Environment>>,
+    is_initializer: bool
+}
+
+impl LoxFunction {
+    pub fn new(declaration: Rc<FunctionDeclaration>, closure: Rc<RefCell<Environment>>, is_initializer: bool) -> Self {
+        LoxFunction {
+            declaration,
+            closure,
+            is_initializer
+        }
+    }
+
+    pub fn bind(&self, instance: Rc<RefCell<LoxInstance>>) -> LoxFunction {
+        let mut environment = Environment::new_enclosed(&self.closure);
+        environment.define("this", EvaluatorResult::Instance(instance));
+
+        LoxFunction::new(Rc::clone(&self.declaration), Rc::new(RefCell::new(environment)), self.is_initializer)
+    }
+}
+
+impl Callable for LoxFunction {
+    fn arity(&self) -> usize {
+        self.declaration.params.len()
+    }
+
+    fn call(&self, evaluator: &Evaluator, arguments: Vec<EvaluatorResult>, line: u16) -> Result<EvaluatorResult, EvaluationError> {
+        let mut environment = Environment::new_enclosed(&self.closure);
+
+        for (param, argument) in self.declaration.params.iter().zip(arguments) {
+            environment.define(&param.get_name(), argument);
+        }
+
+        let flow = Interpreter::execute_block(evaluator, &self.declaration.body, Rc::new(RefCell::new(environment)))?;
+
+        // An initializer always hands back the instance, even when it is called directly or
+        // exits early with an empty `return`.
+        if self.is_initializer {
+            return self.closure.borrow().get_by_name("this", line);
+        }
+
+        match flow {
+            ExecutionFlow::Return(value) => Ok(value),
+            _ => Ok(EvaluatorResult::Nil)
+        }
+    }
+}
+
+impl fmt::Display for LoxFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<fn {}>", self.declaration.name.get_name())
+    }
+}
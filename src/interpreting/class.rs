@@ -0,0 +1,103 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use crate::interpreting::callable::{Callable, LoxFunction};
+use crate::interpreting::evaluator::{EvaluationError, Evaluator, EvaluatorResult};
+use crate::lexing::token::Token;
+
+//** LOX CLASS *********************************************************************************************************
+
+pub struct LoxClass {
+    name: String,
+    superclass: Option<Rc<LoxClass>>,
+    methods: HashMap<String, Rc<LoxFunction>>
+}
+
+impl LoxClass {
+    pub fn new(name: &str, superclass: Option<Rc<LoxClass>>, methods: HashMap<String, Rc<LoxFunction>>) -> Self {
+        LoxClass {
+            name: name.to_string(),
+            superclass,
+            methods
+        }
+    }
+
+    /// Looks for the method on this class first and then walks up the superclass chain, so a
+    /// subclass can override any method it inherits.
+    pub fn find_method(&self, name: &str) -> Option<Rc<LoxFunction>> {
+        match self.methods.get(name) {
+            Some(method) => Some(Rc::clone(method)),
+            None => self.superclass.as_ref().and_then(|superclass| superclass.find_method(name))
+        }
+    }
+}
+
+/// Calling a class constructs a new instance of it and then runs `init`, if the class has one,
+/// with the arguments passed to the call.  The instance needs to hold on to the class that
+/// created it, so this is implemented for the reference counted class rather than for the
+/// class itself.
+impl Callable for Rc<LoxClass> {
+    fn arity(&self) -> usize {
+        self.find_method("init").map_or(0, |initializer| initializer.arity())
+    }
+
+    fn call(&self, evaluator: &Evaluator, arguments: Vec<EvaluatorResult>, line: u16) -> Result<EvaluatorResult, EvaluationError> {
+        let instance = Rc::new(RefCell::new(LoxInstance::new(Rc::clone(self))));
+
+        if let Some(initializer) = self.find_method("init") {
+            initializer.bind(Rc::clone(&instance)).call(evaluator, arguments, line)?;
+        }
+
+        Ok(EvaluatorResult::Instance(instance))
+    }
+}
+
+impl fmt::Display for LoxClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+//** LOX INSTANCE ******************************************************************************************************
+
+/// Instances are always shared through an `Rc<RefCell<..>>` so that every variable holding the
+/// same instance sees changes made to its fields.
+pub struct LoxInstance {
+    class: Rc<LoxClass>,
+    fields: HashMap<String, EvaluatorResult>
+}
+
+impl LoxInstance {
+    pub fn new(class: Rc<LoxClass>) -> Self {
+        LoxInstance {
+            class,
+            fields: HashMap::new()
+        }
+    }
+
+    /// Fields shadow methods with the same name.  A method is bound to the instance before it
+    /// is returned, which is why this takes the shared instance rather than `&self`.
+    pub fn get(instance: &Rc<RefCell<LoxInstance>>, name: &Token) -> Result<EvaluatorResult, EvaluationError> {
+        if let Some(value) = instance.borrow().fields.get(&name.get_name()) {
+            return Ok(value.clone());
+        }
+
+        let method = instance.borrow().class.find_method(&name.get_name());
+
+        match method {
+            Some(method) => Ok(EvaluatorResult::Function(Rc::new(method.bind(Rc::clone(instance))))),
+            None => Err(EvaluationError::UndefinedProperty { name: name.get_name(), line: name.line })
+        }
+    }
+
+    pub fn set(&mut self, name: &Token, value: EvaluatorResult) {
+        self.fields.insert(name.get_name(), value);
+    }
+}
+
+impl fmt::Display for LoxInstance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} instance", self.class)
+    }
+}
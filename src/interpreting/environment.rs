@@ -0,0 +1,95 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::interpreting::evaluator::{EvaluationError, EvaluatorResult};
+use crate::lexing::token::Token;
+
+//** ENVIRONMENT *******************************************************************************************************
+
+/// Stores the values bound to variable names.  Lox allows a variable to be redefined at the
+/// global level, so `define` simply overwrites any existing value.
+///
+/// Each block gets its own environment that points back to the one enclosing it.  The resolver
+/// works out how many environments away each local variable lives, so lookups jump straight to
+/// the right one instead of searching.  Anything the resolver did not find in a local scope is
+/// assumed to be global and is looked up in the outermost environment.
+#[derive(Default)]
+pub struct Environment {
+    values: HashMap<String, EvaluatorResult>,
+    enclosing: Option<Rc<RefCell<Environment>>>
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Environment {
+            values: HashMap::new(),
+            enclosing: None
+        }
+    }
+
+    pub fn new_enclosed(enclosing: &Rc<RefCell<Environment>>) -> Self {
+        Environment {
+            values: HashMap::new(),
+            enclosing: Some(Rc::clone(enclosing))
+        }
+    }
+
+    pub fn define(&mut self, name: &str, value: EvaluatorResult) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    pub fn assign(&mut self, name: &Token, value: EvaluatorResult) -> Result<(), EvaluationError> {
+        match self.values.get_mut(&name.get_name()) {
+            Some(existing_value) => {
+                *existing_value = value;
+                Ok(())
+            },
+            None => Err(EvaluationError::UndefinedVariable { name: name.get_name(), line: name.line })
+        }
+    }
+
+    pub fn get_by_name(&self, name: &str, line: u16) -> Result<EvaluatorResult, EvaluationError> {
+        match self.values.get(name) {
+            Some(value) => Ok(value.clone()),
+            None => Err(EvaluationError::UndefinedVariable { name: name.to_string(), line })
+        }
+    }
+
+    /// Walks `depth` environments out from the given one.  A depth of `None` means the variable
+    /// is global, so the walk continues until it reaches the outermost environment.
+    fn ancestor(environment: &Rc<RefCell<Environment>>, depth: Option<usize>) -> Rc<RefCell<Environment>> {
+        let mut current = Rc::clone(environment);
+        let mut distance = 0;
+
+        while depth != Some(distance) {
+            let enclosing = current.borrow().enclosing.clone();
+
+            match enclosing {
+                Some(enclosing) => current = enclosing,
+                None => break
+            }
+
+            distance += 1;
+        }
+
+        current
+    }
+
+    pub fn get_at(
+        environment: &Rc<RefCell<Environment>>,
+        depth: Option<usize>,
+        name: &str,
+        line: u16) -> Result<EvaluatorResult, EvaluationError>
+    {
+        Self::ancestor(environment, depth).borrow().get_by_name(name, line)
+    }
+
+    pub fn assign_at(
+        environment: &Rc<RefCell<Environment>>,
+        depth: Option<usize>,
+        name: &Token,
+        value: EvaluatorResult) -> Result<(), EvaluationError>
+    {
+        Self::ancestor(environment, depth).borrow_mut().assign(name, value)
+    }
+}
@@ -1,26 +1,98 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 use std::fmt;
+use std::rc::Rc;
 use exitcode::ExitCode;
+use crate::interpreting::callable::{Callable, LoxFunction};
+use crate::interpreting::class::{LoxClass, LoxInstance};
+use crate::interpreting::environment::Environment;
+use crate::interpreting::list;
+use crate::interpreting::list::LoxList;
+use crate::interpreting::map;
+use crate::interpreting::map::{LoxMap, MapKey};
+use crate::interpreting::native::NativeFunction;
 use crate::interpreting::evaluator::EvaluatorResult::{Boolean, Numeric};
 use crate::lexing::token::{Token, TokenType};
-use crate::lexing::token::TokenType::{Minus, Plus, Slash, Star, Greater, GreaterEqual, Less, LessEqual};
+use crate::lexing::token::TokenType::{Minus, MinusEqual, MinusMinus, Percent, Plus, PlusEqual, PlusPlus, SlashEqual, StarEqual, Slash, SlashSlash, Star, StarStar, Greater, GreaterEqual, Less, LessEqual};
 use crate::parsing::expression::Expression;
 use crate::util::error_handling::ExitCodeProvider;
 
 //** EVALUATION ERRORS *************************************************************************************************
 
+/// Every runtime error records the line it happened on, apart from `InvalidExpression`, which
+/// means the evaluator was handed a tree the parser should never produce.  The message and the
+/// line are kept apart; they are only put together when the error is reported.
 #[derive(Debug)]
 pub enum EvaluationError {
+    ArityMismatch { expected: usize, actual: usize, line: u16 },
+    IndexMustBeInteger { line: u16 },
+    IndexOutOfRange { index: i64, length: usize, line: u16 },
+    InvalidArgument { function: String, expected: String, line: u16 },
     InvalidExpression,
-    NumericOperandRequired,
-    NumericOperandsRequired
+    InvalidKey { line: u16 },
+    NotCallable { line: u16 },
+    NotIndexable { line: u16 },
+    NotSliceable { line: u16 },
+    SuperclassMustBeClass { line: u16 },
+    NumericOperandRequired { line: u16 },
+    NumericOperandsRequired { line: u16 },
+    OnlyInstancesHaveFields { line: u16 },
+    OnlyInstancesHaveProperties { line: u16 },
+    StackOverflow { line: u16 },
+    UndefinedKey { key: String, line: u16 },
+    UndefinedProperty { name: String, line: u16 },
+    UndefinedVariable { name: String, line: u16 }
+}
+
+impl EvaluationError {
+    pub fn line(&self) -> Option<u16> {
+        match self {
+            EvaluationError::ArityMismatch { line, .. } |
+            EvaluationError::IndexMustBeInteger { line } |
+            EvaluationError::IndexOutOfRange { line, .. } |
+            EvaluationError::InvalidArgument { line, .. } |
+            EvaluationError::InvalidKey { line } |
+            EvaluationError::NotCallable { line } |
+            EvaluationError::NotIndexable { line } |
+            EvaluationError::NotSliceable { line } |
+            EvaluationError::SuperclassMustBeClass { line } |
+            EvaluationError::NumericOperandRequired { line } |
+            EvaluationError::NumericOperandsRequired { line } |
+            EvaluationError::OnlyInstancesHaveFields { line } |
+            EvaluationError::OnlyInstancesHaveProperties { line } |
+            EvaluationError::StackOverflow { line } |
+            EvaluationError::UndefinedKey { line, .. } |
+            EvaluationError::UndefinedProperty { line, .. } |
+            EvaluationError::UndefinedVariable { line, .. } => Some(*line),
+            EvaluationError::InvalidExpression => None
+        }
+    }
 }
 
 impl fmt::Display for EvaluationError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            EvaluationError::ArityMismatch { expected, actual, line: _ } =>
+                write!(f, "Expected {} arguments but got {}.", expected, actual),
+            EvaluationError::IndexMustBeInteger { .. } => write!(f, "Index must be an integer."),
+            EvaluationError::IndexOutOfRange { index, length, line: _ } =>
+                write!(f, "Index {} is out of range for a list of length {}.", index, length),
+            EvaluationError::InvalidArgument { function, expected, line: _ } =>
+                write!(f, "Argument to '{}' must be {}.", function, expected),
             EvaluationError::InvalidExpression => write!(f, "Invalid expression"),
-            EvaluationError::NumericOperandRequired => write!(f, "Operand must be a number."),
-            EvaluationError::NumericOperandsRequired => write!(f, "Operands must be numbers.")
+            EvaluationError::NotCallable { .. } => write!(f, "Can only call functions and classes."),
+            EvaluationError::InvalidKey { .. } => write!(f, "Map keys must be strings, numbers, booleans or nil."),
+            EvaluationError::NotIndexable { .. } => write!(f, "Only lists and maps can be indexed."),
+            EvaluationError::NotSliceable { .. } => write!(f, "Only lists can be sliced."),
+            EvaluationError::SuperclassMustBeClass { .. } => write!(f, "Superclass must be a class."),
+            EvaluationError::NumericOperandRequired { .. } => write!(f, "Operand must be a number."),
+            EvaluationError::NumericOperandsRequired { .. } => write!(f, "Operands must be numbers."),
+            EvaluationError::OnlyInstancesHaveFields { .. } => write!(f, "Only instances have fields."),
+            EvaluationError::OnlyInstancesHaveProperties { .. } => write!(f, "Only instances have properties."),
+            EvaluationError::StackOverflow { .. } => write!(f, "Stack overflow."),
+            EvaluationError::UndefinedKey { key, line: _ } => write!(f, "Undefined key '{}'.", key),
+            EvaluationError::UndefinedProperty { name, line: _ } => write!(f, "Undefined property '{}'.", name),
+            EvaluationError::UndefinedVariable { name, line: _ } => write!(f, "Undefined variable '{}'.", name)
         }
     }
 }
@@ -30,34 +102,112 @@ impl ExitCodeProvider for EvaluationError {
         None
     }
 
+    /// Reported the same way as the reference implementation: the message, then the line it
+    /// happened on.
     fn get_error_details(&self) -> Option<String> {
-        Some(format!("{}", self))
+        match self.line() {
+            Some(line) => Some(format!("{}\n[line {}]", self, line)),
+            None => Some(format!("{}", self))
+        }
     }
 
     fn get_exit_code(&self) -> ExitCode {
         match self {
+            EvaluationError::ArityMismatch { .. } => 70,
+            EvaluationError::IndexMustBeInteger { .. } => 70,
+            EvaluationError::IndexOutOfRange { .. } => 70,
+            EvaluationError::InvalidArgument { .. } => 70,
             EvaluationError::InvalidExpression => 70,
-            EvaluationError::NumericOperandRequired => 70,
-            EvaluationError::NumericOperandsRequired => 70
+            EvaluationError::NotCallable { .. } => 70,
+            EvaluationError::InvalidKey { .. } => 70,
+            EvaluationError::NotIndexable { .. } => 70,
+            EvaluationError::NotSliceable { .. } => 70,
+            EvaluationError::SuperclassMustBeClass { .. } => 70,
+            EvaluationError::NumericOperandRequired { .. } => 70,
+            EvaluationError::NumericOperandsRequired { .. } => 70,
+            EvaluationError::OnlyInstancesHaveFields { .. } => 70,
+            EvaluationError::OnlyInstancesHaveProperties { .. } => 70,
+            EvaluationError::StackOverflow { .. } => 70,
+            EvaluationError::UndefinedKey { .. } => 70,
+            EvaluationError::UndefinedProperty { .. } => 70,
+            EvaluationError::UndefinedVariable { .. } => 70
         }
     }
 }
 
 //** EVALUATOR RESULT **************************************************************************************************
 
+#[derive(Clone)]
 pub enum EvaluatorResult {
     String(String),
     Numeric(f64),
     Boolean(bool),
+    Function(Rc<LoxFunction>),
+    NativeFunction(Rc<NativeFunction>),
+    Class(Rc<LoxClass>),
+    Instance(Rc<RefCell<LoxInstance>>),
+    List(LoxList),
+    Map(Rc<RefCell<LoxMap>>),
     Nil
 }
 
+impl EvaluatorResult {
+    fn as_callable(&self) -> Option<&dyn Callable> {
+        match self {
+            EvaluatorResult::Function(function) => Some(function.as_ref()),
+            EvaluatorResult::NativeFunction(function) => Some(function.as_ref()),
+            EvaluatorResult::Class(class) => Some(class),
+            _ => None
+        }
+    }
+
+    /// Formats a value that appears inside a list or a map.  Strings are quoted and escaped with
+    /// the escape sequences a string literal accepts, so `"1"` can be told apart from `1`.
+    /// `visited` holds the lists and maps that are already being formatted further out, which
+    /// is how a value that contains itself is recognised.
+    pub(crate) fn format_nested(&self, visited: &mut HashSet<*const ()>) -> String {
+        match self {
+            EvaluatorResult::String(value) => format!("\"{}\"", escape(value)),
+            EvaluatorResult::List(elements) => list::format_list(elements, visited),
+            EvaluatorResult::Map(map) => map::format_map(map, visited),
+            _ => self.to_string()
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::new();
+    let mut characters = value.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\0' => escaped.push_str("\\0"),
+            '$' if characters.peek() == Some(&'{') => escaped.push_str("\\$"),
+            character if character.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", character as u32)),
+            character => escaped.push(character)
+        }
+    }
+
+    escaped
+}
+
 impl fmt::Display for EvaluatorResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             EvaluatorResult::String(value) => write!(f, "{}", value),
             Numeric(value) => write!(f, "{}", value),
             Boolean(value) => write!(f, "{}", value),
+            EvaluatorResult::Function(function) => write!(f, "{}", function),
+            EvaluatorResult::NativeFunction(function) => write!(f, "{}", function),
+            EvaluatorResult::Class(class) => write!(f, "{}", class),
+            EvaluatorResult::Instance(instance) => write!(f, "{}", instance.borrow()),
+            EvaluatorResult::List(elements) => write!(f, "{}", list::format_list(elements, &mut HashSet::new())),
+            EvaluatorResult::Map(map) => write!(f, "{}", map::format_map(map, &mut HashSet::new())),
             EvaluatorResult::Nil => write!(f, "nil")
         }
     }
@@ -65,43 +215,109 @@ impl fmt::Display for EvaluatorResult {
 
 //** EVALUATOR *********************************************************************************************************
 
+/// How many calls can be in progress at once.  Every Lox call takes several Rust stack frames,
+/// so runaway recursion is stopped here and reported as a runtime error instead of overflowing
+/// the interpreter's own stack.
+const MAX_CALL_DEPTH: usize = 1000;
+
+/// `call_depth` counts the calls that are currently in progress, so that it can be checked
+/// against `MAX_CALL_DEPTH`.
 pub struct Evaluator {
-    pub ast: Option<Expression>
+    pub ast: Option<Expression>,
+    call_depth: Cell<usize>
 }
 
 impl Evaluator {
     pub fn new(ast: Option<Expression>) -> Self {
-        Self { ast }
+        Self { ast, call_depth: Cell::new(0) }
     }
 
     fn string_literal(&self, value: &str) -> Result<EvaluatorResult, EvaluationError> {
-        match value {
-            "nil" => Ok(EvaluatorResult::Nil),
-            "true" | "false" => Ok(Boolean(value == "true")),
-            _ => Ok(EvaluatorResult::String(value.to_string()))
-        }
+        Ok(EvaluatorResult::String(value.to_string()))
     }
 
     fn numeric_literal(&self, value: f64) -> Result<EvaluatorResult, EvaluationError> {
         Ok(Numeric(value))
     }
 
-    fn is_truthy(result: EvaluatorResult) -> bool {
+    /// Lox follows Ruby's rule: `false` and `nil` are falsy and every other value, including
+    /// `0` and the empty string, is truthy.
+    pub(crate) fn is_truthy(result: &EvaluatorResult) -> bool {
         match result {
-            EvaluatorResult::String(value) => value != "false",
-            Numeric(value) => value != 0.0,
-            Boolean(value) => value,
+            Boolean(value) => *value,
             EvaluatorResult::Nil => false,
+            _ => true
         }
     }
 
-    fn unary(&self,  operator: &Token, right: &Expression) -> Result<EvaluatorResult, EvaluationError> {
-        let right_result = self.evaluate_expression(right)?;
+    fn variable(
+        &self,
+        name: &Token,
+        depth: Option<usize>,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        Environment::get_at(environment, depth, &name.get_name(), name.line)
+    }
+
+    fn assign(
+        &self,
+        name: &Token,
+        value: &Expression,
+        depth: Option<usize>,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let value_result = self.evaluate_expression(value, environment)?;
+        Environment::assign_at(environment, depth, name, value_result.clone())?;
+
+        Ok(value_result)
+    }
+
+    /// Only evaluates the right operand when the left one doesn't already decide the result.
+    /// The operand that decided the result is returned as is rather than being converted to a
+    /// boolean, so `nil or "default"` evaluates to `"default"`.
+    fn logical(
+        &self,
+        left: &Expression,
+        operator: &Token,
+        right: &Expression,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let left_result = self.evaluate_expression(left, environment)?;
+
+        match (operator.token_type, Self::is_truthy(&left_result)) {
+            (TokenType::Or, true) | (TokenType::And, false) => Ok(left_result),
+            (TokenType::Or, false) | (TokenType::And, true) => self.evaluate_expression(right, environment),
+            _ => Err(EvaluationError::InvalidExpression)
+        }
+    }
+
+    /// Only the branch that is chosen is evaluated.
+    fn conditional(
+        &self,
+        condition: &Expression,
+        then_branch: &Expression,
+        else_branch: &Expression,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        if Self::is_truthy(&self.evaluate_expression(condition, environment)?) {
+            self.evaluate_expression(then_branch, environment)
+        } else {
+            self.evaluate_expression(else_branch, environment)
+        }
+    }
+
+    fn unary(
+        &self,
+        operator: &Token,
+        right: &Expression,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let right_result = self.evaluate_expression(right, environment)?;
 
         match (operator.token_type, &right_result) {
             (Minus, Numeric(value)) => Ok(Numeric(-value)),
-            (Minus, _) => Err(EvaluationError::NumericOperandRequired),
-            (TokenType::Bang, _) => Ok(Boolean(!Self::is_truthy(right_result))),
+            (Minus, _) => Err(EvaluationError::NumericOperandRequired { line: operator.line }),
+            (TokenType::Bang, _) => Ok(Boolean(!Self::is_truthy(&right_result))),
             _ => Err(EvaluationError::InvalidExpression)
         }
     }
@@ -113,33 +329,64 @@ impl Evaluator {
             (Numeric(left), Numeric(right)) => left == right,
             (Boolean(left), Boolean(right)) => left == right,
             (EvaluatorResult::String(left), EvaluatorResult::String(right)) => left == right,
+            (EvaluatorResult::Function(left), EvaluatorResult::Function(right)) => Rc::ptr_eq(left, right),
+            (EvaluatorResult::NativeFunction(left), EvaluatorResult::NativeFunction(right)) => Rc::ptr_eq(left, right),
+            (EvaluatorResult::Class(left), EvaluatorResult::Class(right)) => Rc::ptr_eq(left, right),
+            (EvaluatorResult::Instance(left), EvaluatorResult::Instance(right)) => Rc::ptr_eq(left, right),
+            (EvaluatorResult::List(left), EvaluatorResult::List(right)) => Rc::ptr_eq(left, right),
+            (EvaluatorResult::Map(left), EvaluatorResult::Map(right)) => Rc::ptr_eq(left, right),
             _ => false
         }
     }
 
-    fn binary(&self, left: &Expression, operator: &Token, right: &Expression) -> Result<EvaluatorResult, EvaluationError> {
-        let left_result = self.evaluate_expression(left)?;
-        let right_result = self.evaluate_expression(right)?;
+    fn binary(
+        &self,
+        left: &Expression,
+        operator: &Token,
+        right: &Expression,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let left_result = self.evaluate_expression(left, environment)?;
+        let right_result = self.evaluate_expression(right, environment)?;
 
-        match (&left_result, &right_result, &operator.token_type) {
+        Self::binary_operation(&left_result, &right_result, operator.token_type, operator.line)
+    }
+
+    /// `%` is the remainder of truncating division, so its result takes the sign of the dividend:
+    /// `-7 % 3` is `-1` and `7 % -3` is `1`.  `//` divides and then rounds down towards negative
+    /// infinity, so `-7 // 2` is `-4`.  Dividing by zero with either follows the same floating
+    /// point rules as `/`.
+    fn binary_operation(
+        left_result: &EvaluatorResult,
+        right_result: &EvaluatorResult,
+        operator: TokenType,
+        line: u16) -> Result<EvaluatorResult, EvaluationError>
+    {
+        match (left_result, right_result, operator) {
             // Numeric operations
             (Numeric(left), Numeric(right), Slash) => Ok(Numeric(left / right)),
-            (_, _, Slash) => Err(EvaluationError::NumericOperandsRequired),
+            (_, _, Slash) => Err(EvaluationError::NumericOperandsRequired { line }),
+            (Numeric(left), Numeric(right), SlashSlash) => Ok(Numeric((left / right).floor())),
+            (_, _, SlashSlash) => Err(EvaluationError::NumericOperandsRequired { line }),
+            (Numeric(left), Numeric(right), Percent) => Ok(Numeric(left % right)),
+            (_, _, Percent) => Err(EvaluationError::NumericOperandsRequired { line }),
+            (Numeric(left), Numeric(right), StarStar) => Ok(Numeric(left.powf(*right))),
+            (_, _, StarStar) => Err(EvaluationError::NumericOperandsRequired { line }),
             (Numeric(left), Numeric(right), Star) => Ok(Numeric(left * right)),
-            (_, _, Star) => Err(EvaluationError::NumericOperandsRequired),
+            (_, _, Star) => Err(EvaluationError::NumericOperandsRequired { line }),
             (Numeric(left), Numeric(right), Minus) => Ok(Numeric(left - right)),
-            (_, _, Minus) => Err(EvaluationError::NumericOperandsRequired),
+            (_, _, Minus) => Err(EvaluationError::NumericOperandsRequired { line }),
             (Numeric(left), Numeric(right), Plus) => Ok(Numeric(left + right)),
 
             // Comparison operations
             (Numeric(left), Numeric(right), Greater) => Ok(Boolean(left > right)),
-            (_, _, Greater) => Err(EvaluationError::NumericOperandsRequired),
+            (_, _, Greater) => Err(EvaluationError::NumericOperandsRequired { line }),
             (Numeric(left), Numeric(right), GreaterEqual) => Ok(Boolean(left >= right)),
-            (_, _, GreaterEqual) => Err(EvaluationError::NumericOperandsRequired),
+            (_, _, GreaterEqual) => Err(EvaluationError::NumericOperandsRequired { line }),
             (Numeric(left), Numeric(right), Less) => Ok(Boolean(left < right)),
-            (_, _, Less) => Err(EvaluationError::NumericOperandsRequired),
+            (_, _, Less) => Err(EvaluationError::NumericOperandsRequired { line }),
             (Numeric(left), Numeric(right), LessEqual) => Ok(Boolean(left <= right)),
-            (_, _, LessEqual) => Err(EvaluationError::NumericOperandsRequired),
+            (_, _, LessEqual) => Err(EvaluationError::NumericOperandsRequired { line }),
 
             // Equality operations
             (left, right, TokenType::BangEqual) => Ok(Boolean(!Self::is_equal(left, right))),
@@ -148,27 +395,382 @@ impl Evaluator {
             // String operations
             (EvaluatorResult::String(left), EvaluatorResult::String(right), Plus) =>
                 Ok(EvaluatorResult::String(format!("{}{}", left, right))),
-            (_, _, Plus) => Err(EvaluationError::NumericOperandsRequired),
+            (_, _, Plus) => Err(EvaluationError::NumericOperandsRequired { line }),
 
             // Invalid
             _ => Err(EvaluationError::InvalidExpression)
         }
     }
 
-    pub fn evaluate_expression(&self, expression: &Expression) -> Result<EvaluatorResult, EvaluationError> {
+    fn call(
+        &self,
+        callee: &Expression,
+        paren: &Token,
+        arguments: &[Expression],
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let callee_result = self.evaluate_expression(callee, environment)?;
+
+        let mut argument_results = Vec::new();
+        for argument in arguments {
+            argument_results.push(self.evaluate_expression(argument, environment)?);
+        }
+
+        let callable = callee_result.as_callable().ok_or(EvaluationError::NotCallable { line: paren.line })?;
+
+        if argument_results.len() != callable.arity() {
+            return Err(EvaluationError::ArityMismatch {
+                expected: callable.arity(),
+                actual: argument_results.len(),
+                line: paren.line
+            });
+        }
+
+        if self.call_depth.get() >= MAX_CALL_DEPTH {
+            return Err(EvaluationError::StackOverflow { line: paren.line });
+        }
+
+        self.call_depth.set(self.call_depth.get() + 1);
+        let result = callable.call(self, argument_results, paren.line);
+        self.call_depth.set(self.call_depth.get() - 1);
+
+        result
+    }
+
+    fn get(
+        &self,
+        object: &Expression,
+        name: &Token,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        match self.evaluate_expression(object, environment)? {
+            EvaluatorResult::Instance(instance) => LoxInstance::get(&instance, name),
+            _ => Err(EvaluationError::OnlyInstancesHaveProperties { line: name.line })
+        }
+    }
+
+    fn set(
+        &self,
+        object: &Expression,
+        name: &Token,
+        value: &Expression,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let instance = match self.evaluate_expression(object, environment)? {
+            EvaluatorResult::Instance(instance) => instance,
+            _ => return Err(EvaluationError::OnlyInstancesHaveFields { line: name.line })
+        };
+
+        let value_result = self.evaluate_expression(value, environment)?;
+        instance.borrow_mut().set(name, value_result.clone());
+
+        Ok(value_result)
+    }
+
+    /// The resolver places `super` in a scope of its own just outside the scope that holds
+    /// `this`.  That means the instance the method should be bound to is always exactly one
+    /// environment closer than the superclass.
+    fn super_method(
+        &self,
+        method: &Token,
+        depth: Option<usize>,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let superclass = match Environment::get_at(environment, depth, "super", method.line)? {
+            EvaluatorResult::Class(superclass) => superclass,
+            _ => return Err(EvaluationError::SuperclassMustBeClass { line: method.line })
+        };
+
+        let instance = match Environment::get_at(environment, depth.map(|depth| depth - 1), "this", method.line)? {
+            EvaluatorResult::Instance(instance) => instance,
+            _ => return Err(EvaluationError::InvalidExpression)
+        };
+
+        match superclass.find_method(&method.get_name()) {
+            Some(found_method) => Ok(EvaluatorResult::Function(Rc::new(found_method.bind(instance)))),
+            None => Err(EvaluationError::UndefinedProperty { name: method.get_name(), line: method.line })
+        }
+    }
+
+    /// Every part is converted to a string exactly the way `print` would show it.
+    fn interpolation(&self, parts: &[Expression], environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError> {
+        let mut output = String::new();
+
+        for part in parts {
+            output.push_str(&self.evaluate_expression(part, environment)?.to_string());
+        }
+
+        Ok(EvaluatorResult::String(output))
+    }
+
+    fn list(&self, elements: &[Expression], environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError> {
+        let mut element_results = Vec::new();
+
+        for element in elements {
+            element_results.push(self.evaluate_expression(element, environment)?);
+        }
+
+        Ok(list::new_list(element_results))
+    }
+
+    fn map(
+        &self,
+        brace: &Token,
+        entries: &[(Expression, Expression)],
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let mut map = LoxMap::new();
+
+        for (key, value) in entries {
+            let key_result = self.evaluate_expression(key, environment)?;
+            let value_result = self.evaluate_expression(value, environment)?;
+
+            map.set(MapKey::from_value(&key_result, brace.line)?, value_result);
+        }
+
+        Ok(map::new_map(map))
+    }
+
+    fn index(
+        &self,
+        object: &Expression,
+        bracket: &Token,
+        index: &Expression,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let object_result = self.evaluate_expression(object, environment)?;
+        let index_result = self.evaluate_expression(index, environment)?;
+
+        Self::read_element(&object_result, &index_result, bracket)
+    }
+
+    fn read_element(
+        object_result: &EvaluatorResult,
+        index_result: &EvaluatorResult,
+        bracket: &Token) -> Result<EvaluatorResult, EvaluationError>
+    {
+        match object_result {
+            EvaluatorResult::List(elements) => {
+                let elements = elements.borrow();
+                let position = list::position(index_result, elements.len(), bracket.line)?;
+
+                Ok(elements[position].clone())
+            },
+            EvaluatorResult::Map(map) => {
+                let key = MapKey::from_value(index_result, bracket.line)?;
+
+                match map.borrow().get(&key) {
+                    Some(value) => Ok(value.clone()),
+                    None => Err(EvaluationError::UndefinedKey { key: index_result.to_string(), line: bracket.line })
+                }
+            },
+            _ => Err(EvaluationError::NotIndexable { line: bracket.line })
+        }
+    }
+
+    /// Assigning to a list element requires the element to exist already, whereas assigning to
+    /// a map key adds the key when it is missing.
+    fn index_set(
+        &self,
+        object: &Expression,
+        bracket: &Token,
+        index: &Expression,
+        value: &Expression,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let object_result = self.evaluate_expression(object, environment)?;
+        let index_result = self.evaluate_expression(index, environment)?;
+        let value_result = self.evaluate_expression(value, environment)?;
+
+        Self::write_element(&object_result, &index_result, bracket, value_result.clone())?;
+
+        Ok(value_result)
+    }
+
+    fn write_element(
+        object_result: &EvaluatorResult,
+        index_result: &EvaluatorResult,
+        bracket: &Token,
+        value_result: EvaluatorResult) -> Result<(), EvaluationError>
+    {
+        match object_result {
+            EvaluatorResult::List(elements) => {
+                let mut elements = elements.borrow_mut();
+                let position = list::position(index_result, elements.len(), bracket.line)?;
+
+                elements[position] = value_result;
+            },
+            EvaluatorResult::Map(map) => {
+                let key = MapKey::from_value(index_result, bracket.line)?;
+
+                map.borrow_mut().set(key, value_result);
+            },
+            _ => return Err(EvaluationError::NotIndexable { line: bracket.line })
+        }
+
+        Ok(())
+    }
+
+    /// A slice is a new list.  It holds the same values as the original, but adding or
+    /// removing elements in one does not affect the other.
+    fn slice(
+        &self,
+        object: &Expression,
+        bracket: &Token,
+        start: &Expression,
+        end: &Expression,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let elements = match self.evaluate_expression(object, environment)? {
+            EvaluatorResult::List(elements) => elements,
+            _ => return Err(EvaluationError::NotSliceable { line: bracket.line })
+        };
+
+        let start_result = self.evaluate_expression(start, environment)?;
+        let end_result = self.evaluate_expression(end, environment)?;
+
+        let elements = elements.borrow();
+        let (start, end) = list::slice_range(&start_result, &end_result, elements.len(), bracket.line)?;
+
+        Ok(list::new_list(elements[start..end].to_vec()))
+    }
+
+    /// Reads the current value of an assignment target, works out its new value with `update`
+    /// and stores it back.  The object and index of a target are evaluated once, before the
+    /// current value is read, and the value is read and written without any borrow being held
+    /// in between, so `update` is free to evaluate expressions that use the same list or map.
+    /// `update` returns the value to store followed by the result of the whole expression.
+    fn update_target<F>(
+        &self,
+        target: &Expression,
+        environment: &Rc<RefCell<Environment>>,
+        update: F) -> Result<EvaluatorResult, EvaluationError>
+    where
+        F: FnOnce(EvaluatorResult) -> Result<(EvaluatorResult, EvaluatorResult), EvaluationError>
+    {
+        match target {
+            Expression::Variable { name, depth } => {
+                let current = Environment::get_at(environment, depth.get(), &name.get_name(), name.line)?;
+                let (value_result, result) = update(current)?;
+
+                Environment::assign_at(environment, depth.get(), name, value_result)?;
+                Ok(result)
+            },
+            Expression::Get { object, name } => {
+                let instance = match self.evaluate_expression(object, environment)? {
+                    EvaluatorResult::Instance(instance) => instance,
+                    _ => return Err(EvaluationError::OnlyInstancesHaveFields { line: name.line })
+                };
+
+                let current = LoxInstance::get(&instance, name)?;
+                let (value_result, result) = update(current)?;
+
+                instance.borrow_mut().set(name, value_result);
+                Ok(result)
+            },
+            Expression::Index { object, bracket, index } => {
+                let object_result = self.evaluate_expression(object, environment)?;
+                let index_result = self.evaluate_expression(index, environment)?;
+
+                let current = Self::read_element(&object_result, &index_result, bracket)?;
+                let (value_result, result) = update(current)?;
+
+                Self::write_element(&object_result, &index_result, bracket, value_result)?;
+                Ok(result)
+            },
+            _ => Err(EvaluationError::InvalidExpression)
+        }
+    }
+
+    /// The right-hand side is evaluated after the target's current value has been read, so
+    /// `a += f()` sees `a` as it was before `f` ran.
+    fn compound_assign(
+        &self,
+        target: &Expression,
+        operator: &Token,
+        value: &Expression,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let operation = match operator.token_type {
+            PlusEqual => Plus,
+            MinusEqual => Minus,
+            StarEqual => Star,
+            SlashEqual => Slash,
+            _ => return Err(EvaluationError::InvalidExpression)
+        };
+
+        self.update_target(target, environment, |current| {
+            let value_result = self.evaluate_expression(value, environment)?;
+            let updated = Self::binary_operation(&current, &value_result, operation, operator.line)?;
+
+            Ok((updated.clone(), updated))
+        })
+    }
+
+    /// The prefix form evaluates to the new value and the postfix form to the old one.
+    fn update(
+        &self,
+        target: &Expression,
+        operator: &Token,
+        prefix: bool,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
+        let step = match operator.token_type {
+            PlusPlus => 1.0,
+            MinusMinus => -1.0,
+            _ => return Err(EvaluationError::InvalidExpression)
+        };
+
+        self.update_target(target, environment, |current| match current {
+            Numeric(value) => Ok((Numeric(value + step), Numeric(if prefix { value + step } else { value }))),
+            _ => Err(EvaluationError::NumericOperandRequired { line: operator.line })
+        })
+    }
+
+    pub fn evaluate_expression(
+        &self,
+        expression: &Expression,
+        environment: &Rc<RefCell<Environment>>) -> Result<EvaluatorResult, EvaluationError>
+    {
         match expression {
             Expression::StringLiteral { value } => self.string_literal(value),
             Expression::NumericLiteral { value } => self.numeric_literal(value.clone()),
-            Expression::Grouping { expression: inner_expression} => self.evaluate_expression(inner_expression),
-            Expression::Unary { operator, right } => self.unary(operator, right),
-            Expression::Binary { left, operator, right } => self.binary(left, operator, right)
+            Expression::BooleanLiteral { value } => Ok(Boolean(*value)),
+            Expression::NilLiteral => Ok(EvaluatorResult::Nil),
+            Expression::Interpolation { parts } => self.interpolation(parts, environment),
+            Expression::Grouping { expression: inner_expression} => self.evaluate_expression(inner_expression, environment),
+            Expression::Unary { operator, right } => self.unary(operator, right, environment),
+            Expression::Binary { left, operator, right } => self.binary(left, operator, right, environment),
+            Expression::Variable { name, depth } => self.variable(name, depth.get(), environment),
+            Expression::Assign { name, value, depth } => self.assign(name, value, depth.get(), environment),
+            Expression::CompoundAssign { target, operator, value } => {
+                self.compound_assign(target, operator, value, environment)
+            },
+            Expression::Update { target, operator, prefix } => self.update(target, operator, *prefix, environment),
+            Expression::Logical { left, operator, right } => self.logical(left, operator, right, environment),
+            Expression::Conditional { condition, then_branch, else_branch } => {
+                self.conditional(condition, then_branch, else_branch, environment)
+            },
+            Expression::Call { callee, paren, arguments } => self.call(callee, paren, arguments, environment),
+            Expression::This { keyword, depth } => self.variable(keyword, depth.get(), environment),
+            Expression::Get { object, name } => self.get(object, name, environment),
+            Expression::Set { object, name, value } => self.set(object, name, value, environment),
+            Expression::Super { keyword: _, method, depth } => self.super_method(method, depth.get(), environment),
+            Expression::List { elements } => self.list(elements, environment),
+            Expression::Map { brace, entries } => self.map(brace, entries, environment),
+            Expression::Index { object, bracket, index } => self.index(object, bracket, index, environment),
+            Expression::IndexSet { object, bracket, index, value } => {
+                self.index_set(object, bracket, index, value, environment)
+            },
+            Expression::Slice { object, bracket, start, end } => self.slice(object, bracket, start, end, environment)
         }
     }
 
     pub fn evaluate(&self) -> Result<String, EvaluationError> {
         match &self.ast {
             Some(ast) => {
-                let output = self.evaluate_expression(ast)?;
+                let environment = Rc::new(RefCell::new(Environment::new()));
+                let output = self.evaluate_expression(ast, &environment)?;
                 Ok(format!("{}", output))
             },
             None => Err(EvaluationError::InvalidExpression)
@@ -1,38 +1,218 @@
-use crate::interpreting::evaluator::Evaluator;
-use crate::parsing::statement::Statement;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::interpreting::callable::LoxFunction;
+use crate::interpreting::class::LoxClass;
+use crate::interpreting::environment::Environment;
+use crate::interpreting::evaluator::{EvaluationError, Evaluator, EvaluatorResult};
+use crate::interpreting::native::NativeRegistry;
+use crate::lexing::token::Token;
+use crate::parsing::expression::Expression;
+use crate::parsing::statement::{FunctionDeclaration, Statement};
 use crate::util::error_handling::InterpreterError;
 
+//** EXECUTION FLOW ****************************************************************************************************
+
+/// Describes how control leaves a statement.  A `return` has to unwind through any number of
+/// enclosing blocks and loops until it reaches the function call that it belongs to.  That is
+/// not an error, so rather than using the `Err` path it is passed back up as a normal result.
+///
+/// `break` and `continue` unwind the same way until they reach the loop they target, which is
+/// either the innermost loop or the one with the matching label.
+pub(crate) enum ExecutionFlow {
+    Normal,
+    Return(EvaluatorResult),
+    Break(Option<String>),
+    Continue(Option<String>)
+}
+
+//** INTERPRETER *******************************************************************************************************
+
 pub struct Interpreter {
     pub statements:  Vec<Statement>,
-    pub evaluator: Evaluator
+    pub evaluator: Evaluator,
+    pub environment: Rc<RefCell<Environment>>
 }
 
+/// Executing a statement only needs an evaluator and the environment to run it in.  The
+/// execution functions are associated functions rather than methods so that the evaluator can
+/// also use them to run the body of a function when it evaluates a call expression.
 impl Interpreter {
     pub fn new(statements: Vec<Statement>, evaluator: Evaluator) -> Self {
+        let mut globals = Environment::new();
+        NativeRegistry::with_builtins().install(&mut globals);
+
         Self {
             statements,
-            evaluator
+            evaluator,
+            environment: Rc::new(RefCell::new(globals))
+        }
+    }
+
+    /// The block's environment only lives for as long as this call, so the enclosing scope is
+    /// back in effect as soon as it returns.  That is true whether the statements succeed or
+    /// one of them fails part way through.
+    pub(crate) fn execute_block(
+        evaluator: &Evaluator,
+        statements: &[Statement],
+        environment: Rc<RefCell<Environment>>) -> Result<ExecutionFlow, EvaluationError>
+    {
+        for statement in statements {
+            match Self::execute_statement(evaluator, statement, &environment)? {
+                ExecutionFlow::Normal => {},
+                flow => return Ok(flow)
+            }
+        }
+
+        Ok(ExecutionFlow::Normal)
+    }
+
+    fn is_loop_target(label: &Option<Token>, target: &Option<String>) -> bool {
+        match target {
+            Some(target) => label.as_ref().is_some_and(|label| &label.get_name() == target),
+            None => true
         }
     }
 
-    fn execute_statement(&self, statement: &Statement) -> Result<(), InterpreterError> {
+    fn execute_while(
+        evaluator: &Evaluator,
+        condition: &Expression,
+        body: &Statement,
+        increment: &Option<Expression>,
+        label: &Option<Token>,
+        environment: &Rc<RefCell<Environment>>) -> Result<ExecutionFlow, EvaluationError>
+    {
+        while Evaluator::is_truthy(&evaluator.evaluate_expression(condition, environment)?) {
+            match Self::execute_statement(evaluator, body, environment)? {
+                ExecutionFlow::Break(target) if Self::is_loop_target(label, &target) => break,
+                ExecutionFlow::Continue(target) if Self::is_loop_target(label, &target) => {},
+                ExecutionFlow::Normal => {},
+                flow => return Ok(flow)
+            }
+
+            if let Some(increment) = increment {
+                evaluator.evaluate_expression(increment, environment)?;
+            }
+        }
+
+        Ok(ExecutionFlow::Normal)
+    }
+
+    /// When the class has a superclass, its methods close over an extra environment that
+    /// defines `super`.  This is how `super.method()` finds the right class no matter which
+    /// subclass the instance actually belongs to.
+    fn execute_class(
+        evaluator: &Evaluator,
+        name: &Token,
+        superclass: &Option<Expression>,
+        methods: &[Rc<FunctionDeclaration>],
+        environment: &Rc<RefCell<Environment>>) -> Result<ExecutionFlow, EvaluationError>
+    {
+        let superclass = match superclass {
+            Some(expression) => match evaluator.evaluate_expression(expression, environment)? {
+                EvaluatorResult::Class(class) => Some(class),
+                _ => return Err(EvaluationError::SuperclassMustBeClass { line: name.line })
+            },
+            None => None
+        };
+
+        let method_environment = match &superclass {
+            Some(class) => {
+                let mut super_environment = Environment::new_enclosed(environment);
+                super_environment.define("super", EvaluatorResult::Class(Rc::clone(class)));
+
+                Rc::new(RefCell::new(super_environment))
+            },
+            None => Rc::clone(environment)
+        };
+
+        let methods: HashMap<String, Rc<LoxFunction>> = methods
+            .iter()
+            .map(|method| {
+                let method_name = method.name.get_name();
+                let is_initializer = method_name == "init";
+
+                (method_name, Rc::new(LoxFunction::new(Rc::clone(method), Rc::clone(&method_environment), is_initializer)))
+            })
+            .collect();
+
+        let class = LoxClass::new(&name.get_name(), superclass, methods);
+
+        environment.borrow_mut().define(&name.get_name(), EvaluatorResult::Class(Rc::new(class)));
+        Ok(ExecutionFlow::Normal)
+    }
+
+    fn execute_statement(
+        evaluator: &Evaluator,
+        statement: &Statement,
+        environment: &Rc<RefCell<Environment>>) -> Result<ExecutionFlow, EvaluationError>
+    {
         match statement {
             Statement::PrintStmt { expression } => {
-                println!("{}", self.evaluator.evaluate_expression(expression)?);
-                Ok(())
+                println!("{}", evaluator.evaluate_expression(expression, environment)?);
+                Ok(ExecutionFlow::Normal)
             },
             Statement::ExpressionStmt { expression } => {
-                self.evaluator.evaluate_expression(expression)?;
-                Ok(())
+                evaluator.evaluate_expression(expression, environment)?;
+                Ok(ExecutionFlow::Normal)
+            },
+            Statement::VarStmt { name, initializer } => {
+                let value = match initializer {
+                    Some(expression) => evaluator.evaluate_expression(expression, environment)?,
+                    None => EvaluatorResult::Nil
+                };
+
+                environment.borrow_mut().define(&name.get_name(), value);
+                Ok(ExecutionFlow::Normal)
+            },
+            Statement::BlockStmt { statements } => {
+                Self::execute_block(evaluator, statements, Rc::new(RefCell::new(Environment::new_enclosed(environment))))
+            },
+            Statement::IfStmt { condition, then_branch, else_branch } => {
+                let condition_result = evaluator.evaluate_expression(condition, environment)?;
+
+                if Evaluator::is_truthy(&condition_result) {
+                    Self::execute_statement(evaluator, then_branch, environment)
+                } else if let Some(else_branch) = else_branch {
+                    Self::execute_statement(evaluator, else_branch, environment)
+                } else {
+                    Ok(ExecutionFlow::Normal)
+                }
+            },
+            Statement::WhileStmt { condition, body, increment, label } => {
+                Self::execute_while(evaluator, condition, body, increment, label, environment)
+            },
+            Statement::BreakStmt { label } => {
+                Ok(ExecutionFlow::Break(label.as_ref().map(|label| label.get_name())))
+            },
+            Statement::ContinueStmt { label } => {
+                Ok(ExecutionFlow::Continue(label.as_ref().map(|label| label.get_name())))
+            },
+            Statement::FunctionStmt { declaration } => {
+                let function = LoxFunction::new(Rc::clone(declaration), Rc::clone(environment), false);
+
+                environment.borrow_mut().define(&declaration.name.get_name(), EvaluatorResult::Function(Rc::new(function)));
+                Ok(ExecutionFlow::Normal)
+            },
+            Statement::ReturnStmt { keyword: _, value } => {
+                let value = match value {
+                    Some(expression) => evaluator.evaluate_expression(expression, environment)?,
+                    None => EvaluatorResult::Nil
+                };
+
+                Ok(ExecutionFlow::Return(value))
+            },
+            Statement::ClassStmt { name, superclass, methods } => {
+                Self::execute_class(evaluator, name, superclass, methods, environment)
             }
         }
     }
 
     pub fn interpret(&self) -> Result<String, InterpreterError> {
         for statement in &self.statements {
-            self.execute_statement(statement)?
+            Self::execute_statement(&self.evaluator, statement, &self.environment)?;
         }
 
         Ok("".to_string())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,85 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use crate::interpreting::evaluator::{EvaluationError, EvaluatorResult};
+
+//** LOX LIST **********************************************************************************************************
+
+/// Lists are always shared through an `Rc<RefCell<..>>`, just like instances, so every variable
+/// holding the same list sees elements that are added, removed or replaced through any of them.
+pub type LoxList = Rc<RefCell<Vec<EvaluatorResult>>>;
+
+pub fn new_list(elements: Vec<EvaluatorResult>) -> EvaluatorResult {
+    EvaluatorResult::List(Rc::new(RefCell::new(elements)))
+}
+
+/// A list that is reached again while it is still being formatted contains itself, so it is
+/// shown as `[...]` rather than formatted without end.
+pub fn format_list(elements: &LoxList, visited: &mut HashSet<*const ()>) -> String {
+    let pointer = Rc::as_ptr(elements) as *const ();
+
+    if !visited.insert(pointer) {
+        return "[...]".to_string();
+    }
+
+    let formatted: Vec<String> = elements.borrow().iter().map(|element| element.format_nested(visited)).collect();
+    visited.remove(&pointer);
+
+    format!("[{}]", formatted.join(", "))
+}
+
+fn integer_index(index: &EvaluatorResult, line: u16) -> Result<i64, EvaluationError> {
+    match index {
+        EvaluatorResult::Numeric(value) if value.fract() == 0.0 => Ok(*value as i64),
+        _ => Err(EvaluationError::IndexMustBeInteger { line })
+    }
+}
+
+/// Converts a Lox index into a position in a list of the given length.  Negative indices count
+/// back from the end of the list, so `-1` is the last element.  Positions up to, but not
+/// including, `limit` are accepted.  Reading an element uses the length as the limit, while
+/// inserting also allows the position just past the end.
+fn position_within(index: &EvaluatorResult, length: usize, limit: usize, line: u16) -> Result<usize, EvaluationError> {
+    let index = integer_index(index, line)?;
+    let position = if index < 0 { index + length as i64 } else { index };
+
+    if position < 0 || position >= limit as i64 {
+        return Err(EvaluationError::IndexOutOfRange { index, length, line });
+    }
+
+    Ok(position as usize)
+}
+
+pub fn position(index: &EvaluatorResult, length: usize, line: u16) -> Result<usize, EvaluationError> {
+    position_within(index, length, length, line)
+}
+
+pub fn insert_position(index: &EvaluatorResult, length: usize, line: u16) -> Result<usize, EvaluationError> {
+    position_within(index, length, length + 1, line)
+}
+
+fn slice_bound(bound: &EvaluatorResult, length: usize, default: usize, line: u16) -> Result<usize, EvaluationError> {
+    if let EvaluatorResult::Nil = bound {
+        return Ok(default);
+    }
+
+    let bound = integer_index(bound, line)?;
+    let position = if bound < 0 { bound + length as i64 } else { bound };
+
+    Ok(position.clamp(0, length as i64) as usize)
+}
+
+/// Works out the range of elements covered by `list[start:end]`.  A `nil` bound means the start
+/// or end of the list.  Unlike indexing, bounds that fall outside the list are clamped to it
+/// rather than raising an error, and a start past the end produces an empty range.
+pub fn slice_range(
+    start: &EvaluatorResult,
+    end: &EvaluatorResult,
+    length: usize,
+    line: u16) -> Result<(usize, usize), EvaluationError>
+{
+    let start = slice_bound(start, length, 0, line)?;
+    let end = slice_bound(end, length, length, line)?;
+
+    Ok((start, end.max(start)))
+}
@@ -0,0 +1,133 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use crate::interpreting::evaluator::{EvaluationError, EvaluatorResult};
+
+//** MAP KEY ***********************************************************************************************************
+
+/// The values that can be used as map keys.  Numbers are stored by their bit pattern after
+/// being normalised, so that keys which compare as the same number also hash the same:
+///
+/// - `-0` and `0` are the same key, just as `-0 == 0` in Lox.
+/// - Every `NaN` is the same key.  `NaN` is never equal to itself in Lox, but a key that can be
+///   stored and never found again would be of little use.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MapKey {
+    String(String),
+    Number(u64),
+    Boolean(bool),
+    Nil
+}
+
+impl MapKey {
+    fn normalise(value: f64) -> f64 {
+        if value == 0.0 { 0.0 } else if value.is_nan() { f64::NAN } else { value }
+    }
+
+    pub fn from_value(value: &EvaluatorResult, line: u16) -> Result<Self, EvaluationError> {
+        match value {
+            EvaluatorResult::String(value) => Ok(MapKey::String(value.to_string())),
+            EvaluatorResult::Numeric(value) => Ok(MapKey::Number(Self::normalise(*value).to_bits())),
+            EvaluatorResult::Boolean(value) => Ok(MapKey::Boolean(*value)),
+            EvaluatorResult::Nil => Ok(MapKey::Nil),
+            _ => Err(EvaluationError::InvalidKey { line })
+        }
+    }
+
+    pub fn to_value(&self) -> EvaluatorResult {
+        match self {
+            MapKey::String(value) => EvaluatorResult::String(value.to_string()),
+            MapKey::Number(bits) => EvaluatorResult::Numeric(f64::from_bits(*bits)),
+            MapKey::Boolean(value) => EvaluatorResult::Boolean(*value),
+            MapKey::Nil => EvaluatorResult::Nil
+        }
+    }
+}
+
+//** LOX MAP ***********************************************************************************************************
+
+/// A map that remembers the order its keys were first inserted in.  The entries are kept in a
+/// vector in that order and the hash map only records where each key's entry is.  Replacing
+/// the value of an existing key keeps its original position.
+///
+/// Like lists, maps are shared through an `Rc<RefCell<..>>` so they have reference semantics.
+#[derive(Default)]
+pub struct LoxMap {
+    entries: Vec<(MapKey, EvaluatorResult)>,
+    positions: HashMap<MapKey, usize>
+}
+
+impl LoxMap {
+    pub fn new() -> Self {
+        LoxMap {
+            entries: Vec::new(),
+            positions: HashMap::new()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn get(&self, key: &MapKey) -> Option<&EvaluatorResult> {
+        self.positions.get(key).map(|position| &self.entries[*position].1)
+    }
+
+    pub fn has(&self, key: &MapKey) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    pub fn set(&mut self, key: MapKey, value: EvaluatorResult) {
+        match self.positions.get(&key) {
+            Some(position) => self.entries[*position].1 = value,
+            None => {
+                self.positions.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+
+    /// Removing an entry shifts every later entry back by one, so their recorded positions are
+    /// updated to match.
+    pub fn delete(&mut self, key: &MapKey) -> Option<EvaluatorResult> {
+        let position = self.positions.remove(key)?;
+        let (_, value) = self.entries.remove(position);
+
+        for later_position in self.positions.values_mut() {
+            if *later_position > position { *later_position -= 1; }
+        }
+
+        Some(value)
+    }
+
+    pub fn keys(&self) -> Vec<EvaluatorResult> {
+        self.entries.iter().map(|(key, _)| key.to_value()).collect()
+    }
+
+    pub fn values(&self) -> Vec<EvaluatorResult> {
+        self.entries.iter().map(|(_, value)| value.clone()).collect()
+    }
+}
+
+/// Uses the same guard as lists, so a map that is reached again while it is still being
+/// formatted is shown as `{...}`.
+pub fn format_map(map: &Rc<RefCell<LoxMap>>, visited: &mut HashSet<*const ()>) -> String {
+    let pointer = Rc::as_ptr(map) as *const ();
+
+    if !visited.insert(pointer) {
+        return "{...}".to_string();
+    }
+
+    let entries: Vec<String> = map.borrow().entries
+        .iter()
+        .map(|(key, value)| format!("{}: {}", key.to_value().format_nested(visited), value.format_nested(visited)))
+        .collect();
+
+    visited.remove(&pointer);
+
+    format!("{{{}}}", entries.join(", "))
+}
+
+pub fn new_map(map: LoxMap) -> EvaluatorResult {
+    EvaluatorResult::Map(Rc::new(RefCell::new(map)))
+}
@@ -0,0 +1,187 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::interpreting::callable::Callable;
+use crate::interpreting::environment::Environment;
+use crate::interpreting::evaluator::{EvaluationError, Evaluator, EvaluatorResult};
+use crate::interpreting::list;
+use crate::interpreting::list::LoxList;
+use crate::interpreting::map::{LoxMap, MapKey};
+
+//** NATIVE FUNCTION ***************************************************************************************************
+
+pub type NativeImplementation = dyn Fn(Vec<EvaluatorResult>, u16) -> Result<EvaluatorResult, EvaluationError>;
+
+/// A function implemented in Rust rather than Lox.  The evaluator checks the arity before the
+/// call is made, so the implementation can rely on receiving exactly `arity` arguments.  It is
+/// also given the line of the call for any runtime errors it needs to report.
+pub struct NativeFunction {
+    name: String,
+    arity: usize,
+    implementation: Box<NativeImplementation>
+}
+
+impl NativeFunction {
+    pub fn new(name: &str, arity: usize, implementation: Box<NativeImplementation>) -> Self {
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            implementation
+        }
+    }
+}
+
+impl Callable for NativeFunction {
+    fn arity(&self) -> usize {
+        self.arity
+    }
+
+    fn call(&self, _evaluator: &Evaluator, arguments: Vec<EvaluatorResult>, line: u16) -> Result<EvaluatorResult, EvaluationError> {
+        (self.implementation)(arguments, line)
+    }
+}
+
+impl fmt::Display for NativeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<native fn>")
+    }
+}
+
+//** NATIVE REGISTRY ***************************************************************************************************
+
+/// Collects the native functions that are made available to every Lox program.  Adding a new
+/// built-in only requires registering it here; the evaluator calls natives through the same
+/// `Callable` interface it uses for functions declared in Lox.
+#[derive(Default)]
+pub struct NativeRegistry {
+    functions: Vec<Rc<NativeFunction>>
+}
+
+impl NativeRegistry {
+    pub fn new() -> Self {
+        NativeRegistry {
+            functions: Vec::new()
+        }
+    }
+
+    pub fn with_builtins() -> Self {
+        let mut registry = NativeRegistry::new();
+
+        registry.register("clock", 0, Box::new(|_, _| {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+            Ok(EvaluatorResult::Numeric(now.as_secs_f64()))
+        }));
+
+        registry.register("len", 1, Box::new(|arguments, line| {
+            match &arguments[0] {
+                EvaluatorResult::List(elements) => Ok(EvaluatorResult::Numeric(elements.borrow().len() as f64)),
+                EvaluatorResult::Map(map) => Ok(EvaluatorResult::Numeric(map.borrow().len() as f64)),
+                EvaluatorResult::String(value) => Ok(EvaluatorResult::Numeric(value.chars().count() as f64)),
+                _ => Err(EvaluationError::InvalidArgument {
+                    function: "len".to_string(),
+                    expected: "a list, a map or a string".to_string(),
+                    line
+                })
+            }
+        }));
+
+        registry.register_list_builtins();
+        registry.register_map_builtins();
+        registry
+    }
+
+    fn list_argument(function: &str, argument: &EvaluatorResult, line: u16) -> Result<LoxList, EvaluationError> {
+        match argument {
+            EvaluatorResult::List(elements) => Ok(Rc::clone(elements)),
+            _ => Err(EvaluationError::InvalidArgument { function: function.to_string(), expected: "a list".to_string(), line })
+        }
+    }
+
+    /// The list built-ins change the list they are given in place, so the change is visible
+    /// through every variable that refers to the same list.
+    fn register_list_builtins(&mut self) {
+        self.register("push", 2, Box::new(|arguments, line| {
+            let elements = Self::list_argument("push", &arguments[0], line)?;
+            elements.borrow_mut().push(arguments[1].clone());
+
+            Ok(EvaluatorResult::Nil)
+        }));
+
+        self.register("pop", 1, Box::new(|arguments, line| {
+            let elements = Self::list_argument("pop", &arguments[0], line)?;
+            let mut elements = elements.borrow_mut();
+
+            elements.pop().ok_or(EvaluationError::IndexOutOfRange { index: -1, length: 0, line })
+        }));
+
+        self.register("insert", 3, Box::new(|arguments, line| {
+            let elements = Self::list_argument("insert", &arguments[0], line)?;
+            let mut elements = elements.borrow_mut();
+
+            let position = list::insert_position(&arguments[1], elements.len(), line)?;
+            elements.insert(position, arguments[2].clone());
+
+            Ok(EvaluatorResult::Nil)
+        }));
+
+        self.register("remove", 2, Box::new(|arguments, line| {
+            let elements = Self::list_argument("remove", &arguments[0], line)?;
+            let mut elements = elements.borrow_mut();
+
+            let position = list::position(&arguments[1], elements.len(), line)?;
+            Ok(elements.remove(position))
+        }));
+    }
+
+    fn map_argument(function: &str, argument: &EvaluatorResult, line: u16) -> Result<Rc<RefCell<LoxMap>>, EvaluationError> {
+        match argument {
+            EvaluatorResult::Map(map) => Ok(Rc::clone(map)),
+            _ => Err(EvaluationError::InvalidArgument { function: function.to_string(), expected: "a map".to_string(), line })
+        }
+    }
+
+    /// `keys` and `values` return new lists in the map's insertion order.  `delete` reports
+    /// whether the key was present, since the value stored under it could itself be `nil`.
+    fn register_map_builtins(&mut self) {
+        self.register("keys", 1, Box::new(|arguments, line| {
+            let map = Self::map_argument("keys", &arguments[0], line)?;
+            let keys = map.borrow().keys();
+
+            Ok(list::new_list(keys))
+        }));
+
+        self.register("values", 1, Box::new(|arguments, line| {
+            let map = Self::map_argument("values", &arguments[0], line)?;
+            let values = map.borrow().values();
+
+            Ok(list::new_list(values))
+        }));
+
+        self.register("has", 2, Box::new(|arguments, line| {
+            let map = Self::map_argument("has", &arguments[0], line)?;
+            let key = MapKey::from_value(&arguments[1], line)?;
+            let has_key = map.borrow().has(&key);
+
+            Ok(EvaluatorResult::Boolean(has_key))
+        }));
+
+        self.register("delete", 2, Box::new(|arguments, line| {
+            let map = Self::map_argument("delete", &arguments[0], line)?;
+            let key = MapKey::from_value(&arguments[1], line)?;
+            let deleted = map.borrow_mut().delete(&key);
+
+            Ok(EvaluatorResult::Boolean(deleted.is_some()))
+        }));
+    }
+
+    pub fn register(&mut self, name: &str, arity: usize, implementation: Box<NativeImplementation>) {
+        self.functions.push(Rc::new(NativeFunction::new(name, arity, implementation)));
+    }
+
+    pub fn install(&self, environment: &mut Environment) {
+        for function in &self.functions {
+            environment.define(&function.name, EvaluatorResult::NativeFunction(Rc::clone(function)));
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use crate::interpreting::evaluator::Evaluator;
-use crate::lexing::scanner::Scanner;
+use crate::interpreting::evaluator::{EvaluationError, Evaluator};
+use crate::lexing::scanner::{CommentStyle, Scanner};
 use crate::parsing::parser::Parser;
 
 #[test]
@@ -47,6 +47,101 @@ fn should_successfully_handle_complex_basic_math() {
     assert_eq!(evaluate("(-66 + 66) * (18 * 57) / (1 + 4)"), "0");
 }
 
+#[test]
+fn should_return_deciding_operand_of_or() {
+    assert_eq!(evaluate("nil or \"default\""), "default");
+    assert_eq!(evaluate("\"first\" or \"second\""), "first");
+}
+
+#[test]
+fn should_return_deciding_operand_of_and() {
+    assert_eq!(evaluate("nil and \"never\""), "nil");
+    assert_eq!(evaluate("1 and 2"), "2");
+}
+
+#[test]
+fn should_bind_and_tighter_than_or() {
+    assert_eq!(evaluate("true or false and nil"), "true");
+}
+
+#[test]
+fn should_not_treat_keyword_text_in_strings_as_literals() {
+    assert_eq!(evaluate("\"nil\" == nil"), "false");
+    assert_eq!(evaluate("\"true\" == true"), "false");
+    assert_eq!(evaluate("!\"false\""), "false");
+}
+
+#[test]
+fn should_evaluate_boolean_and_nil_literals() {
+    assert_eq!(evaluate("true == !false"), "true");
+    assert_eq!(evaluate("nil == nil"), "true");
+}
+
+#[test]
+fn should_evaluate_remainder_with_sign_of_dividend() {
+    assert_eq!(evaluate("7 % 3"), "1");
+    assert_eq!(evaluate("-7 % 3"), "-1");
+    assert_eq!(evaluate("7 % -3"), "1");
+    assert_eq!(evaluate("7.5 % 2"), "1.5");
+}
+
+#[test]
+fn should_evaluate_power_right_associatively_and_before_negation() {
+    assert_eq!(evaluate("2 ** 3 ** 2"), "512");
+    assert_eq!(evaluate("-2 ** 2"), "-4");
+    assert_eq!(evaluate("(-2) ** 2"), "4");
+    assert_eq!(evaluate("2 ** -1"), "0.5");
+}
+
+#[test]
+fn should_evaluate_floor_division_when_comments_use_hash() {
+    let tokens = Scanner::new(String::from("-7 // 2 # rounds down"))
+        .with_comment_style(CommentStyle::Hash)
+        .scan_tokens()
+        .unwrap();
+
+    let ast = Parser::new(tokens).parse_ast().unwrap();
+
+    assert_eq!(Evaluator::new(Some(ast)).evaluate().unwrap(), "-4");
+}
+
+#[test]
+fn should_require_numeric_operands_for_arithmetic_operators() {
+    for input in ["\"a\" % 2", "2 ** true", "nil // 1"] {
+        let tokens = Scanner::new(String::from(input)).with_comment_style(CommentStyle::Hash).scan_tokens().unwrap();
+        let ast = Parser::new(tokens).parse_ast().unwrap();
+
+        assert!(matches!(Evaluator::new(Some(ast)).evaluate(), Err(EvaluationError::NumericOperandsRequired { line: 1 })));
+    }
+}
+
+#[test]
+fn should_evaluate_conditional_using_truthiness() {
+    assert_eq!(evaluate("0 ? \"yes\" : \"no\""), "yes");
+    assert_eq!(evaluate("nil ? \"yes\" : \"no\""), "no");
+    assert_eq!(evaluate("false ? 1 : true ? 2 : 3"), "2");
+}
+
+#[test]
+fn should_keep_line_out_of_error_message() {
+    let error = EvaluationError::NumericOperandsRequired { line: 4 };
+
+    assert_eq!(error.to_string(), "Operands must be numbers.");
+    assert_eq!(error.line(), Some(4));
+}
+
+#[test]
+fn should_evaluate_two_leading_minus_signs_as_double_negation() {
+    assert_eq!(evaluate("--(3)"), "3");
+    assert_eq!(evaluate("---(3)"), "-3");
+}
+
+#[test]
+fn should_evaluate_decrement_between_operands_as_subtraction_of_negation() {
+    assert_eq!(evaluate("1--1"), "2");
+    assert_eq!(evaluate("10--2*3"), "16");
+}
+
 fn evaluate(input: &str) -> String {
     let mut scanner = Scanner::new(String::from(input));
     let tokens = scanner.scan_tokens().unwrap();
@@ -2,6 +2,7 @@ use crate::interpreting::evaluator::Evaluator;
 use crate::interpreting::interpreter::Interpreter;
 use crate::lexing::scanner::Scanner;
 use crate::parsing::parser::Parser;
+use crate::resolving::resolver::Resolver;
 use crate::util::error_handling::InterpreterError;
 
 #[test]
@@ -11,6 +12,607 @@ fn should_handle_error() {
     assert!(result.is_err());
 }
 
+#[test]
+fn should_store_declared_variable() {
+    assert_eq!(interpret_and_read("var a = 1 + 2;", "a"), "3");
+}
+
+#[test]
+fn should_default_uninitialized_variable_to_nil() {
+    assert_eq!(interpret_and_read("var a;", "a"), "nil");
+}
+
+#[test]
+fn should_allow_global_variable_to_be_redeclared() {
+    assert_eq!(interpret_and_read("var a = \"before\"; var a = a + \" after\";", "a"), "before after");
+}
+
+#[test]
+fn should_fail_with_runtime_error_when_variable_is_undefined() {
+    let error = interpret("print a;").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Undefined variable 'a'.\n[line 1]".to_string()));
+    assert_eq!(error.exit_code, 70);
+}
+
+#[test]
+fn should_assign_value_to_every_variable_in_chain() {
+    assert_eq!(interpret_and_read("var a; var b; a = b = 3;", "a"), "3");
+}
+
+#[test]
+fn should_fail_with_runtime_error_when_assigning_to_undeclared_variable() {
+    let error = interpret("a = 1;").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Undefined variable 'a'.\n[line 1]".to_string()));
+    assert_eq!(error.exit_code, 70);
+}
+
+#[test]
+fn should_shadow_outer_variable_inside_block() {
+    let input = r##"
+        var a = "outer";
+        var b;
+        {
+            var a = "inner";
+            b = a;
+        }
+        "##;
+
+    assert_eq!(interpret_and_read(input, "a"), "outer");
+    assert_eq!(interpret_and_read(input, "b"), "inner");
+}
+
+#[test]
+fn should_assign_to_enclosing_scope_from_nested_block() {
+    assert_eq!(interpret_and_read("var a = 1; { { a = 2; } }", "a"), "2");
+}
+
+#[test]
+fn should_not_expose_block_variables_after_block_exits() {
+    let error = interpret("{ var a = 1; } print a;").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Undefined variable 'a'.\n[line 1]".to_string()));
+}
+
+#[test]
+fn should_execute_then_branch_when_condition_is_truthy() {
+    assert_eq!(interpret_and_read("var a; if (1 < 2) a = \"then\"; else a = \"else\";", "a"), "then");
+}
+
+#[test]
+fn should_treat_zero_and_empty_string_as_truthy() {
+    assert_eq!(interpret_and_read("var a; if (0) a = \"then\"; else a = \"else\";", "a"), "then");
+    assert_eq!(interpret_and_read("var a; if (\"\") a = \"then\"; else a = \"else\";", "a"), "then");
+}
+
+#[test]
+fn should_execute_else_branch_when_condition_is_falsy() {
+    assert_eq!(interpret_and_read("var a; if (nil) { a = \"then\"; } else { a = \"else\"; }", "a"), "else");
+}
+
+#[test]
+fn should_bind_else_to_nearest_if() {
+    let input = r##"
+        var a = "unchanged";
+        if (true) if (false) a = "inner then"; else a = "inner else";
+        "##;
+
+    assert_eq!(interpret_and_read(input, "a"), "inner else");
+}
+
+#[test]
+fn should_short_circuit_logical_operators() {
+    assert_eq!(interpret_and_read("var a = \"unchanged\"; true or (a = \"changed\");", "a"), "unchanged");
+    assert_eq!(interpret_and_read("var a = \"unchanged\"; false and (a = \"changed\");", "a"), "unchanged");
+}
+
+#[test]
+fn should_repeat_while_loop_until_condition_is_falsy() {
+    assert_eq!(interpret_and_read("var i = 0; var total = 0; while (i < 5) { total = total + i; i = i + 1; }", "total"), "10");
+}
+
+#[test]
+fn should_abort_while_loop_when_body_fails() {
+    let error = interpret("var i = 0; while (i < 5) { i = i + \"one\"; }").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Operands must be numbers.\n[line 1]".to_string()));
+    assert_eq!(error.exit_code, 70);
+}
+
+#[test]
+fn should_run_for_loop() {
+    assert_eq!(interpret_and_read("var total = 0; for (var i = 0; i < 5; i = i + 1) total = total + i;", "total"), "10");
+}
+
+#[test]
+fn should_scope_for_loop_initializer_to_loop() {
+    let error = interpret("for (var i = 0; i < 1; i = i + 1) {} print i;").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Undefined variable 'i'.\n[line 1]".to_string()));
+}
+
+#[test]
+fn should_call_declared_function_with_arguments() {
+    assert_eq!(interpret_and_read("var total; fun add(a, b) { total = a + b; } add(1, 2);", "total"), "3");
+}
+
+#[test]
+fn should_allow_functions_to_be_stored_and_passed_as_arguments() {
+    let input = r##"
+        var result;
+        fun greet(name) { result = "Hello " + name; }
+        fun apply(function, argument) { function(argument); }
+        var alias = greet;
+        apply(alias, "Lox");
+        "##;
+
+    assert_eq!(interpret_and_read(input, "result"), "Hello Lox");
+    assert_eq!(interpret_and_read(input, "alias"), "<fn greet>");
+}
+
+#[test]
+fn should_fail_with_runtime_error_when_argument_count_does_not_match_arity() {
+    let error = interpret("fun add(a, b) {} add(1, 2, 3);").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Expected 2 arguments but got 3.\n[line 1]".to_string()));
+    assert_eq!(error.exit_code, 70);
+}
+
+#[test]
+fn should_fail_with_runtime_error_when_calling_non_callable_value() {
+    let error = interpret("\"not a function\"();").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Can only call functions and classes.\n[line 1]".to_string()));
+}
+
+#[test]
+fn should_return_value_from_nested_loops_and_blocks() {
+    let input = r##"
+        fun find(target) {
+            for (var i = 0; i < 10; i = i + 1) {
+                while (true) {
+                    { if (i == target) return i * 10; }
+                    i = i + 1;
+                }
+            }
+            return "not found";
+        }
+        var found = find(3);
+        "##;
+
+    assert_eq!(interpret_and_read(input, "found"), "30");
+}
+
+#[test]
+fn should_return_nil_when_return_has_no_value() {
+    assert_eq!(interpret_and_read("fun early() { return; print \"unreachable\"; } var result = early();", "result"), "nil");
+}
+
+#[test]
+fn should_return_nil_when_function_has_no_return() {
+    assert_eq!(interpret_and_read("fun nothing() {} var result = nothing();", "result"), "nil");
+}
+
+#[test]
+fn should_support_recursion_using_return_values() {
+    assert_eq!(interpret_and_read("fun fib(n) { if (n < 2) return n; return fib(n - 2) + fib(n - 1); } var result = fib(10);", "result"), "55");
+}
+
+#[test]
+fn should_keep_captured_environment_alive_after_declaring_call_returns() {
+    let input = r##"
+        fun makeCounter() {
+            var i = 0;
+            fun count() { i = i + 1; return i; }
+            return count;
+        }
+        var first = makeCounter();
+        var second = makeCounter();
+        first();
+        first();
+        var result = first() + second();
+        "##;
+
+    assert_eq!(interpret_and_read(input, "result"), "4");
+}
+
+#[test]
+fn should_share_captured_variables_between_closures() {
+    let input = r##"
+        var get;
+        var set;
+        fun makeAccessors() {
+            var value = "initial";
+            fun getter() { return value; }
+            fun setter(newValue) { value = newValue; }
+            get = getter;
+            set = setter;
+        }
+        makeAccessors();
+        set("updated");
+        var result = get();
+        "##;
+
+    assert_eq!(interpret_and_read(input, "result"), "updated");
+}
+
+#[test]
+fn should_bind_closure_to_variable_visible_where_it_was_declared() {
+    let input = r##"
+        var a = "global";
+        var first;
+        var second;
+        {
+            fun showA() { return a; }
+            first = showA();
+            var a = "block";
+            second = showA();
+        }
+        "##;
+
+    assert_eq!(interpret_and_read(input, "first"), "global");
+    assert_eq!(interpret_and_read(input, "second"), "global");
+}
+
+#[test]
+fn should_construct_instance_by_calling_class() {
+    let input = "class Bagel {} var bagel = Bagel();";
+
+    assert_eq!(interpret_and_read(input, "Bagel"), "Bagel");
+    assert_eq!(interpret_and_read(input, "bagel"), "Bagel instance");
+}
+
+#[test]
+fn should_store_and_read_instance_fields() {
+    let input = r##"
+        class Point {}
+        var point = Point();
+        var alias = point;
+        point.x = 1;
+        alias.y = 2;
+        var result = point.x + point.y;
+        "##;
+
+    assert_eq!(interpret_and_read(input, "result"), "3");
+}
+
+#[test]
+fn should_fail_with_runtime_error_when_property_is_undefined() {
+    let error = interpret("class Empty {} Empty().missing;").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Undefined property 'missing'.\n[line 1]".to_string()));
+    assert_eq!(error.exit_code, 70);
+}
+
+#[test]
+fn should_fail_with_runtime_error_when_setting_field_on_non_instance() {
+    let error = interpret("var number = 1; number.field = 2;").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Only instances have fields.\n[line 1]".to_string()));
+}
+
+#[test]
+fn should_bind_this_when_method_is_extracted_from_instance() {
+    let input = r##"
+        class Person {
+            sayName() { return this.name; }
+        }
+        var jane = Person();
+        jane.name = "Jane";
+        var method = jane.sayName;
+        var bill = Person();
+        bill.name = "Bill";
+        bill.sayName = method;
+        var result = bill.sayName();
+        "##;
+
+    assert_eq!(interpret_and_read(input, "result"), "Jane");
+}
+
+#[test]
+fn should_run_init_with_constructor_arguments() {
+    let input = r##"
+        class Point {
+            init(x, y) { this.x = x; this.y = y; }
+            sum() { return this.x + this.y; }
+        }
+        var result = Point(1, 2).sum();
+        "##;
+
+    assert_eq!(interpret_and_read(input, "result"), "3");
+}
+
+#[test]
+fn should_return_instance_when_init_is_called_directly() {
+    let input = r##"
+        class Foo {
+            init() { this.count = 0; return; }
+        }
+        var foo = Foo();
+        var result = foo.init() == foo;
+        "##;
+
+    assert_eq!(interpret_and_read(input, "result"), "true");
+}
+
+#[test]
+fn should_check_constructor_arguments_against_init_arity() {
+    let error = interpret("class Foo { init(a) {} } Foo(1, 2);").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Expected 1 arguments but got 2.\n[line 1]".to_string()));
+}
+
+#[test]
+fn should_inherit_methods_from_superclass_chain() {
+    let input = r##"
+        class A { name() { return "A"; } }
+        class B < A {}
+        class C < B {}
+        var result = C().name();
+        "##;
+
+    assert_eq!(interpret_and_read(input, "result"), "A");
+}
+
+#[test]
+fn should_bind_super_call_to_current_instance() {
+    let input = r##"
+        class Base {
+            init(value) { this.value = value; }
+            describe() { return "base " + this.value; }
+        }
+        class Derived < Base {
+            init(value) { super.init(value + "!"); }
+            describe() { return "derived " + super.describe(); }
+        }
+        class MoreDerived < Derived {}
+        var result = MoreDerived("x").describe();
+        "##;
+
+    assert_eq!(interpret_and_read(input, "result"), "derived base x!");
+}
+
+#[test]
+fn should_fail_with_runtime_error_when_superclass_is_not_a_class() {
+    let error = interpret("var NotAClass = \"nope\"; class Sub < NotAClass {}").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Superclass must be a class.\n[line 1]".to_string()));
+    assert_eq!(error.exit_code, 70);
+}
+
+#[test]
+fn should_provide_clock_as_native_function() {
+    let input = "var start = clock(); var elapsed = clock() - start; var positive = start > 0; var valid = elapsed >= 0;";
+
+    assert_eq!(interpret_and_read(input, "positive"), "true");
+    assert_eq!(interpret_and_read(input, "valid"), "true");
+    assert_eq!(interpret_and_read(input, "clock"), "<native fn>");
+}
+
+#[test]
+fn should_check_native_function_arity() {
+    let error = interpret("clock(1);").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Expected 0 arguments but got 1.\n[line 1]".to_string()));
+}
+
+#[test]
+fn should_leave_loop_on_break() {
+    assert_eq!(interpret_and_read("var a = 0; while (true) { a = a + 1; if (a == 3) break; }", "a"), "3");
+}
+
+#[test]
+fn should_run_for_loop_increment_on_continue() {
+    let input = "var total = 0; for (var i = 0; i < 5; i = i + 1) { if (i == 2) continue; total = total + i; }";
+
+    assert_eq!(interpret_and_read(input, "total"), "8");
+}
+
+#[test]
+fn should_break_out_of_labelled_outer_loop() {
+    let input = "var count = 0; outer: while (true) { while (true) { count = count + 1; break outer; } count = 100; }";
+
+    assert_eq!(interpret_and_read(input, "count"), "1");
+}
+
+#[test]
+fn should_continue_labelled_outer_loop() {
+    let input = "var count = 0; outer: for (var i = 0; i < 3; i = i + 1) { for (;;) { count = count + 1; continue outer; } }";
+
+    assert_eq!(interpret_and_read(input, "count"), "3");
+}
+
+#[test]
+fn should_return_from_function_through_loop() {
+    assert_eq!(interpret_and_read("fun f() { while (true) { { return 1; } } } var a = f();", "a"), "1");
+}
+
+#[test]
+fn should_read_and_assign_list_elements() {
+    assert_eq!(interpret_and_read("var xs = [1, 2, 3]; xs[1] = xs[0] + xs[-1];", "xs"), "[1, 4, 3]");
+}
+
+#[test]
+fn should_slice_list_with_optional_bounds() {
+    assert_eq!(interpret_and_read("var xs = [1, 2, 3, 4]; var a = xs[1:3];", "a"), "[2, 3]");
+    assert_eq!(interpret_and_read("var xs = [1, 2, 3, 4]; var a = xs[:-2];", "a"), "[1, 2]");
+    assert_eq!(interpret_and_read("var xs = [1, 2, 3, 4]; var a = xs[2:];", "a"), "[3, 4]");
+    assert_eq!(interpret_and_read("var xs = [1, 2, 3, 4]; var a = xs[3:1];", "a"), "[]");
+}
+
+#[test]
+fn should_share_list_between_aliases() {
+    assert_eq!(interpret_and_read("var xs = []; var ys = xs; push(ys, 1); var same = xs == ys;", "xs"), "[1]");
+    assert_eq!(interpret_and_read("var same = [] == [];", "same"), "false");
+}
+
+#[test]
+fn should_modify_list_with_native_functions() {
+    let input = "var xs = [1, 2, 3]; push(xs, 4); var last = pop(xs); insert(xs, 0, 0); var removed = remove(xs, -1); var size = len(xs);";
+
+    assert_eq!(interpret_and_read(input, "xs"), "[0, 1, 2]");
+    assert_eq!(interpret_and_read(input, "last"), "4");
+    assert_eq!(interpret_and_read(input, "removed"), "3");
+    assert_eq!(interpret_and_read(input, "size"), "3");
+}
+
+#[test]
+fn should_fail_with_line_number_when_list_index_is_out_of_range() {
+    let error = interpret("var xs = [1, 2];\nprint xs[2];").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Index 2 is out of range for a list of length 2.\n[line 2]".to_string()));
+    assert_eq!(error.exit_code, 70);
+}
+
+#[test]
+fn should_print_map_in_insertion_order() {
+    assert_eq!(interpret_and_read("var m = {\"b\": 1, \"a\": 2}; m[\"c\"] = 3; m[\"b\"] = 4;", "m"), "{\"b\": 4, \"a\": 2, \"c\": 3}");
+}
+
+#[test]
+fn should_support_string_number_boolean_and_nil_keys() {
+    let input = "var m = {\"1\": \"string\", 1: \"number\", true: \"boolean\", nil: \"nil\"}; var a = m[1] + m[\"1\"] + m[true] + m[nil];";
+
+    assert_eq!(interpret_and_read(input, "a"), "numberstringbooleannil");
+}
+
+#[test]
+fn should_treat_negative_zero_and_nan_as_single_keys() {
+    assert_eq!(interpret_and_read("var m = {}; m[-0] = 1; m[0] = 2; m[0/0] = 3; m[0/0] = 4; var size = len(m);", "size"), "2");
+    assert_eq!(interpret_and_read("var m = {0/0: \"nan\"}; var a = m[0/0];", "a"), "nan");
+}
+
+#[test]
+fn should_query_and_modify_map_with_native_functions() {
+    let input = "var m = {\"a\": 1, \"b\": 2, \"c\": 3}; var deleted = delete(m, \"b\"); var found = has(m, \"b\"); var k = keys(m); var v = values(m);";
+
+    assert_eq!(interpret_and_read(input, "deleted"), "true");
+    assert_eq!(interpret_and_read(input, "found"), "false");
+    assert_eq!(interpret_and_read(input, "k"), "[\"a\", \"c\"]");
+    assert_eq!(interpret_and_read(input, "v"), "[1, 3]");
+}
+
+#[test]
+fn should_fail_with_line_number_when_map_key_is_missing() {
+    let error = interpret("var m = {};\nprint m[\"a\"];").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Undefined key 'a'.\n[line 2]".to_string()));
+}
+
+#[test]
+fn should_reject_list_as_map_key() {
+    let error = interpret("var m = {[]: 1};").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Map keys must be strings, numbers, booleans or nil.\n[line 1]".to_string()));
+}
+
+#[test]
+fn should_interpolate_expressions_into_strings() {
+    let input = "var name = \"Ada\"; var age = 36; var a = \"Hello ${name}, you are ${age + 1}\";";
+
+    assert_eq!(interpret_and_read(input, "a"), "Hello Ada, you are 37");
+}
+
+#[test]
+fn should_stringify_interpolated_values_like_print() {
+    let input = "var a = \"${nil} ${true} ${2.5} ${[1, \"x\"]} ${\"nested ${1 + 1}\"}\";";
+
+    assert_eq!(interpret_and_read(input, "a"), "nil true 2.5 [1, \"x\"] nested 2");
+}
+
+#[test]
+fn should_apply_compound_assignment_to_variables_fields_and_elements() {
+    let input = "class C {} var c = C(); c.n = 10; var l = [1, 2]; var s = \"a\"; var n = 6; \
+                 n -= 1; n *= 4; n /= 2; c.n += 5; l[-1] *= 10; s += \"b\"; var r = n += 1; var f = c.n;";
+
+    assert_eq!(interpret_and_read(input, "n"), "11");
+    assert_eq!(interpret_and_read(input, "r"), "11");
+    assert_eq!(interpret_and_read(input, "f"), "15");
+    assert_eq!(interpret_and_read(input, "l"), "[1, 20]");
+    assert_eq!(interpret_and_read(input, "s"), "ab");
+}
+
+#[test]
+fn should_return_new_value_for_prefix_and_old_value_for_postfix_update() {
+    let input = "var m = {\"k\": 1}; var a = m[\"k\"]++; var b = ++m[\"k\"]; var i = 5; var c = i--; var d = --i; var e = -i++;";
+
+    assert_eq!(interpret_and_read(input, "a"), "1");
+    assert_eq!(interpret_and_read(input, "b"), "3");
+    assert_eq!(interpret_and_read(input, "c"), "5");
+    assert_eq!(interpret_and_read(input, "d"), "3");
+    assert_eq!(interpret_and_read(input, "e"), "-3");
+    assert_eq!(interpret_and_read(input, "i"), "4");
+}
+
+#[test]
+fn should_evaluate_update_target_subexpressions_once() {
+    let input = "var calls = 0; var l = [0, 0]; fun at() { calls += 1; return l; } \
+                 at()[calls] += 5; at()[0]++; --at()[1];";
+
+    assert_eq!(interpret_and_read(input, "calls"), "3");
+    assert_eq!(interpret_and_read(input, "l"), "[1, 4]");
+}
+
+#[test]
+fn should_require_number_for_increment() {
+    let error = interpret("var s = \"a\"; s++;").unwrap_err();
+
+    assert_eq!(error.error_details, Some("Operand must be a number.\n[line 1]".to_string()));
+}
+
+#[test]
+fn should_only_evaluate_chosen_branch_of_conditional() {
+    let input = "var calls = 0; fun hit() { calls += 1; return calls; } var a = true ? hit() : hit(); var b = false ? hit() : nil;";
+
+    assert_eq!(interpret_and_read(input, "calls"), "1");
+    assert_eq!(interpret_and_read(input, "a"), "1");
+    assert_eq!(interpret_and_read(input, "b"), "nil");
+}
+
+#[test]
+fn should_report_line_of_every_runtime_error_after_message() {
+    let cases = [
+        ("var a = 1;\n\nprint a + nil;", "Operands must be numbers.\n[line 3]"),
+        ("var a = 1;\nprint b;", "Undefined variable 'b'.\n[line 2]"),
+        ("var l = [];\n\n\nprint l[0];", "Index 0 is out of range for a list of length 0.\n[line 4]")
+    ];
+
+    for (input, expected_details) in cases {
+        assert_eq!(interpret(input).unwrap_err().error_details, Some(expected_details.to_string()));
+    }
+}
+
+#[test]
+fn should_print_list_that_contains_itself() {
+    let input = "var xs = []; push(xs, 1); push(xs, xs); var ys = [xs, xs]; var a = \"${xs}\"; var b = \"${ys}\";";
+
+    assert_eq!(interpret_and_read(input, "a"), "[1, [...]]");
+    assert_eq!(interpret_and_read(input, "b"), "[[1, [...]], [1, [...]]]");
+}
+
+#[test]
+fn should_print_map_that_contains_itself() {
+    let input = "var m = {}; m[\"self\"] = m; var l = [m]; m[\"list\"] = l; var a = \"${m}\"; var b = \"${l}\";";
+
+    assert_eq!(interpret_and_read(input, "a"), "{\"self\": {...}, \"list\": [{...}]}");
+    assert_eq!(interpret_and_read(input, "b"), "[{\"self\": {...}, \"list\": [...]}]");
+}
+
+#[test]
+fn should_subtract_negated_variable_after_decrement_operator() {
+    assert_eq!(interpret_and_read("var a = 5; var b = 3; var c = a--b;", "c"), "8");
+    assert_eq!(interpret_and_read("var a = 5; var b = 3; var c = a--b;", "a"), "5");
+}
+
+#[test]
+fn should_print_strings_in_lists_and_maps_so_that_different_types_can_be_told_apart() {
+    let input = "var m = {\"1\": 1, 1: \"2\", true: \"v\", \"true\": true, nil: \"nil\", \"a\\\"b\\n\\${\": 6}; var a = \"${m}\"; var l = [\"1\", 1, \"\", [\"x\\ty\"]];";
+
+    assert_eq!(interpret_and_read(input, "a"), "{\"1\": 1, 1: \"2\", true: \"v\", \"true\": true, nil: \"nil\", \"a\\\"b\\n\\${\": 6}");
+    assert_eq!(interpret_and_read(input, "l"), "[\"1\", 1, \"\", [\"x\\ty\"]]");
+}
+
 fn interpret(input: &str) -> Result<String, InterpreterError> {
     let mut scanner = Scanner::new(String::from(input));
     let tokens = scanner.scan_tokens().unwrap();
@@ -18,6 +620,29 @@ fn interpret(input: &str) -> Result<String, InterpreterError> {
     let parser = Parser::new(tokens);
     let statements = parser.parse()?;
 
+    let mut resolver = Resolver::new();
+    resolver.resolve(&statements)?;
+
     let interpreter = Interpreter::new(statements, Evaluator::new(None));
     interpreter.interpret()
-}
\ No newline at end of file
+}
+
+/// Runs the program and then returns the global variable with the given name as it would be
+/// printed.  This makes it possible to test the effect of a program without capturing stdout.
+fn interpret_and_read(input: &str, variable_name: &str) -> String {
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let statements = parser.parse().unwrap();
+
+    let mut resolver = Resolver::new();
+    resolver.resolve(&statements).unwrap();
+
+    let interpreter = Interpreter::new(statements, Evaluator::new(None));
+    interpreter.interpret().unwrap();
+
+    let value = interpreter.environment.borrow().get_by_name(variable_name, 0).unwrap();
+
+    format!("{}", value)
+}
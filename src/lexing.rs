@@ -1,5 +1,5 @@
 use std::fs;
-use crate::lexing::scanner::Scanner;
+use crate::lexing::scanner::{CommentStyle, Scanner};
 use crate::util::error_handling::InterpreterError;
 
 pub mod consumer;
@@ -8,13 +8,13 @@ pub mod scanner;
 
 mod tests;
 
-pub fn tokenize_file(filename: &str) -> Result<String, InterpreterError>{
+pub fn tokenize_file(filename: &str, comment_style: CommentStyle) -> Result<String, InterpreterError>{
     let file_contents = fs::read_to_string(filename).unwrap_or_else(|_| {
         eprintln!("Failed to read file {}:  Defaulting to an empty string", filename);
         String::new()
     });
 
-    let mut scanner = Scanner::new(file_contents.clone());
+    let mut scanner = Scanner::new(file_contents.clone()).with_comment_style(comment_style);
     let tokens = scanner.scan_tokens()?;
 
     let mut output = Vec::new();
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Debug};
 use crate::lexing::consumer::{Consumer};
-use crate::lexing::token::{TokenData, Token, TokenType};
+use crate::lexing::token::{StringPart, TokenData, Token, TokenType};
 use crate::util::error_handling::ExitCodeProvider;
 
 //** SCANNING ERRORS ***************************************************************************************************
@@ -10,16 +10,24 @@ use crate::util::error_handling::ExitCodeProvider;
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub enum ScanningError {
+    InvalidEscape { line: u16, sequence: String },
     UnexpectedCharacter { line: u16, character: char },
+    UnterminatedInterpolation { line: u16 },
     UnterminatedString { line: u16, input: String }
 }
 
 impl fmt::Display for ScanningError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            ScanningError::InvalidEscape { line, sequence } => {
+                write!(f, "[line {}] Error: Invalid escape sequence: {}", line, sequence)
+            }
             ScanningError::UnexpectedCharacter { line, character } => {
                 write!(f, "[line {}] Error: Unexpected character: {}", line, character)
             }
+            ScanningError::UnterminatedInterpolation { line } => {
+                write!(f, "[line {}] Error: Unterminated string interpolation.", line)
+            }
             ScanningError::UnterminatedString { line, input: _ } => {
                 write!(f, "[line {}] Error: Unterminated string.", line)
             }
@@ -84,15 +92,40 @@ impl ExitCodeProvider for ScanningErrorSummary {
     }
 }
 
-//** SCANNER ************************** ********************************************************************************
+//** COMMENT STYLE *****************************************************************************************************
+
+/// Decides what starts a line comment.  Lox uses `//`, which leaves no room for a `//` floor
+/// division operator.  With the `Hash` style comments start with `#` instead and `//` is
+/// scanned as floor division.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CommentStyle {
+    #[default]
+    Slashes,
+    Hash
+}
+
+impl CommentStyle {
+    pub fn from_flag(flag: &str) -> Option<Self> {
+        match flag {
+            "--comments=slashes" => Some(CommentStyle::Slashes),
+            "--comments=hash" => Some(CommentStyle::Hash),
+            _ => None
+        }
+    }
 
-enum Trim {
-    None,
-    Both
+    pub fn to_flag(self) -> &'static str {
+        match self {
+            CommentStyle::Slashes => "--comments=slashes",
+            CommentStyle::Hash => "--comments=hash"
+        }
+    }
 }
 
+//** SCANNER ************************** ********************************************************************************
+
 pub struct Scanner {
     source: String,
+    comment_style: CommentStyle,
     keyword_map: HashMap<String, TokenType>
 }
 
@@ -100,9 +133,12 @@ impl Scanner {
     pub fn new(source: String) -> Self {
         Scanner {
             source,
+            comment_style: CommentStyle::default(),
             keyword_map: HashMap::from([
                 ("and".to_string(), TokenType::And),
+                ("break".to_string(), TokenType::Break),
                 ("class".to_string(), TokenType::Class),
+                ("continue".to_string(), TokenType::Continue),
                 ("else".to_string(), TokenType::Else),
                 ("false".to_string(), TokenType::False),
                 ("for".to_string(), TokenType::For),
@@ -120,16 +156,17 @@ impl Scanner {
         }
     }
 
-    fn get_current_lexeme(&self, trim: Trim, consumer: &Consumer) -> &str {
-        let start = match trim {
-            Trim::None => consumer.start_car,
-            Trim::Both => consumer.start_car + 1
-        } as usize;
+    pub fn with_comment_style(mut self, comment_style: CommentStyle) -> Self {
+        self.comment_style = comment_style;
+        self
+    }
+
+    fn get_current_lexeme(&self, consumer: &Consumer) -> &str {
+        self.get_source_between(consumer.start_car, consumer.current_char)
+    }
 
-        let end = match trim {
-            Trim::None => consumer.current_char,
-            Trim::Both => consumer.current_char - 1
-        } as usize;
+    fn get_source_between(&self, start: u16, end: u16) -> &str {
+        let (start, end) = (start as usize, end as usize);
 
         let start_index = get_byte_index(&self.source, start);
         let end_index = get_byte_index(&self.source, end);
@@ -164,7 +201,7 @@ impl Scanner {
     fn build_reserved_token(&self, token_type: TokenType, consumer: &Consumer) -> Token {
         self.build_token(
             token_type,
-            TokenData::new_reserved(self.get_current_lexeme(Trim::None, consumer)),
+            TokenData::new_reserved(self.get_current_lexeme(consumer)),
             consumer)
     }
 
@@ -179,26 +216,186 @@ impl Scanner {
         self.build_reserved_token(if is_match { match_token_type } else { else_token_type }, consumer)
     }
 
-    fn build_string_literal_token(&self, consumer: &mut Consumer) -> Result<Token, ScanningError> {
+    /// Skips over a string that is nested inside an interpolation.  The string can contain
+    /// interpolations of its own, so the two skipping functions call each other.
+    fn skip_nested_string(&self, line: u16, consumer: &mut Consumer) -> Result<(), ScanningError> {
+        loop {
+            if consumer.is_at_end_of_input() { return Err(ScanningError::UnterminatedInterpolation { line }); }
+
+            match consumer.advance() {
+                '"' => return Ok(()),
+                '\\' if !consumer.is_at_end_of_input() => { consumer.advance(); },
+                '$' if consumer.match_char('{') => self.skip_interpolation(line, consumer)?,
+                '\n' => consumer.current_line += 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// Moves past the closing brace of an interpolation.  Braces are counted so that a map
+    /// literal or block inside the interpolation does not end it early, and strings are skipped
+    /// as a whole so that a brace or quote inside them is not mistaken for the end.
+    fn skip_interpolation(&self, line: u16, consumer: &mut Consumer) -> Result<(), ScanningError> {
+        let mut depth = 1;
+
+        while depth > 0 {
+            if consumer.is_at_end_of_input() { return Err(ScanningError::UnterminatedInterpolation { line }); }
+
+            match consumer.advance() {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                '"' => self.skip_nested_string(line, consumer)?,
+                '\n' => consumer.current_line += 1,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Scans the source of a `${...}` interpolation into a run of tokens.  The opening `${` has
+    /// already been consumed.  The embedded source is handed to a scanner of its own, which
+    /// starts counting lines where the interpolation starts.  Every error it finds is passed
+    /// on, just as it would be outside a string.
+    fn scan_interpolation(&self, consumer: &mut Consumer) -> Result<Vec<Token>, Vec<ScanningError>> {
+        let line = consumer.current_line;
+        let start = consumer.current_char;
+
+        self.skip_interpolation(line, consumer).map_err(|error| vec![error])?;
+
+        let mut scanner = Scanner::new(self.get_source_between(start, consumer.current_char - 1).to_string())
+            .with_comment_style(self.comment_style);
+
+        scanner
+            .scan_tokens_from_line(line)
+            .map_err(|summary| summary.errors)
+    }
+
+    /// Decodes the escape sequence that follows a backslash.  `\u{...}` takes between one and six
+    /// hexadecimal digits naming a Unicode scalar value.  `\$` produces a plain dollar sign, so
+    /// a string can contain `${` without starting an interpolation.
+    fn scan_escape(&self, consumer: &mut Consumer) -> Result<char, ScanningError> {
+        let line = consumer.current_line;
+
+        if consumer.is_at_end_of_input() {
+            return Err(ScanningError::InvalidEscape { line, sequence: "\\".to_string() });
+        }
+
+        match consumer.advance() {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            'r' => Ok('\r'),
+            '0' => Ok('\0'),
+            '\\' => Ok('\\'),
+            '"' => Ok('"'),
+            '$' => Ok('$'),
+            'u' => self.scan_unicode_escape(line, consumer),
+            other => {
+                if other == '\n' { consumer.current_line += 1; }
+                Err(ScanningError::InvalidEscape { line, sequence: format!("\\{}", other) })
+            }
+        }
+    }
+
+    fn scan_unicode_escape(&self, line: u16, consumer: &mut Consumer) -> Result<char, ScanningError> {
+        let mut sequence = String::from("\\u");
+
+        if !consumer.match_char('{') {
+            return Err(ScanningError::InvalidEscape { line, sequence });
+        }
+
+        sequence.push('{');
+
+        let mut digits = String::new();
+
+        while consumer.peek().is_ascii_hexdigit() && digits.len() < 6 {
+            digits.push(consumer.advance());
+        }
+
+        sequence.push_str(&digits);
+
+        if !consumer.match_char('}') {
+            return Err(ScanningError::InvalidEscape { line, sequence });
+        }
+
+        sequence.push('}');
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or(ScanningError::InvalidEscape { line, sequence })
+    }
+
+    /// A string without any `${...}` produces a plain string literal token.  Otherwise the
+    /// token holds the text and the scanned interpolations in the order they appear.  In both
+    /// cases the text has its escape sequences decoded, while the lexeme keeps the source as
+    /// it was written.
+    fn build_string_literal_token(&self, consumer: &mut Consumer) -> Result<Token, Vec<ScanningError>> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+
         while consumer.peek() != '"' && !consumer.is_at_end_of_input() {
-            if consumer.peek() == '\n' { consumer.current_line += 1; }
-            consumer.advance();
+            let result = match consumer.peek() {
+                '$' if consumer.peek_next() == '{' => {
+                    consumer.advance();
+                    consumer.advance();
+
+                    parts.push(StringPart::Text(std::mem::take(&mut text)));
+                    self.scan_interpolation(consumer).map(|tokens| parts.push(StringPart::Tokens(tokens)))
+                },
+                '\\' => {
+                    consumer.advance();
+                    self.scan_escape(consumer).map(|character| text.push(character)).map_err(|error| vec![error])
+                },
+                _ => {
+                    if consumer.peek() == '\n' { consumer.current_line += 1; }
+                    text.push(consumer.advance());
+                    Ok(())
+                }
+            };
+
+            if let Err(mut errors) = result {
+                // The rest of the string is skipped so that scanning carries on after it rather
+                // than treating its closing quote as the start of another.  Failing to find the
+                // closing quote means the string is unterminated as well, unless that is already
+                // covered by an interpolation that ran to the end of the input.
+                let is_unterminated = self.skip_nested_string(consumer.current_line, consumer).is_err();
+                let is_reported = errors
+                    .iter()
+                    .any(|error| matches!(error, ScanningError::UnterminatedInterpolation { .. }));
+
+                if is_unterminated && !is_reported {
+                    errors.push(ScanningError::UnterminatedString {
+                        line: consumer.current_line,
+                        input: self.get_current_lexeme(consumer).to_string()
+                    });
+                }
+
+                return Err(errors);
+            }
         }
 
         if consumer.is_at_end_of_input() {
-            return Err(ScanningError::UnterminatedString {
+            return Err(vec![ScanningError::UnterminatedString {
                 line: consumer.current_line,
-                input: self.get_current_lexeme(Trim::None, consumer).to_string()
-            });
+                input: self.get_current_lexeme(consumer).to_string()
+            }]);
         }
 
         consumer.advance();
+
+        if !parts.is_empty() {
+            parts.push(StringPart::Text(text));
+
+            return Ok(self.build_token(
+                TokenType::String,
+                TokenData::new_interpolated_string(self.get_current_lexeme(consumer), parts),
+                consumer));
+        }
+
         Ok(self.build_token(
             TokenType::String,
-            TokenData::new_string_literal(
-                self.get_current_lexeme(Trim::None, consumer),
-                self.get_current_lexeme(Trim::Both, consumer)
-            ),
+            TokenData::new_string_literal(self.get_current_lexeme(consumer), &text),
             consumer))
     }
 
@@ -211,7 +408,7 @@ impl Scanner {
             while consumer.peek().is_ascii_digit() { consumer.advance(); }
         }
 
-        let lexeme = self.get_current_lexeme(Trim::None, consumer);
+        let lexeme = self.get_current_lexeme(consumer);
         let literal = lexeme.parse::<f64>().unwrap();
 
         self.build_token(
@@ -224,7 +421,7 @@ impl Scanner {
     fn build_keyword_or_identifier_token(&self, consumer: &mut Consumer) -> Token {
         while consumer.peek().is_ascii_alphanumeric() || consumer.peek() == '_' { consumer.advance(); }
 
-        let lexeme = self.get_current_lexeme(Trim::None, consumer);
+        let lexeme = self.get_current_lexeme(consumer);
         let token_type = *self.keyword_map.get(lexeme).unwrap_or(&TokenType::Identifier);
 
         self.build_reserved_token(token_type, consumer)
@@ -236,7 +433,9 @@ impl Scanner {
             character: current_char }
     }
 
-    fn scan_token(&self, consumer: &mut Consumer) -> Result<Token, ScanningError> {
+    /// A string can hold interpolations with errors of their own, so scanning a single token can
+    /// produce more than one error.
+    fn scan_token(&self, consumer: &mut Consumer) -> Result<Token, Vec<ScanningError>> {
         let current_char = consumer.advance();
 
         match current_char {
@@ -244,31 +443,48 @@ impl Scanner {
             ')' => Ok(self.build_reserved_token(TokenType::RightParen, consumer)),
             '{' => Ok(self.build_reserved_token(TokenType::LeftBrace, consumer)),
             '}' => Ok(self.build_reserved_token(TokenType::RightBrace, consumer)),
+            '[' => Ok(self.build_reserved_token(TokenType::LeftBracket, consumer)),
+            ']' => Ok(self.build_reserved_token(TokenType::RightBracket, consumer)),
             ',' => Ok(self.build_reserved_token(TokenType::Comma, consumer)),
+            ':' => Ok(self.build_reserved_token(TokenType::Colon, consumer)),
+            '?' => Ok(self.build_reserved_token(TokenType::Question, consumer)),
             '.' => Ok(self.build_reserved_token(TokenType::Dot, consumer)),
-            '-' => Ok(self.build_reserved_token(TokenType::Minus, consumer)),
-            '+' => Ok(self.build_reserved_token(TokenType::Plus, consumer)),
+            '-' if consumer.match_char('-') => Ok(self.build_reserved_token(TokenType::MinusMinus, consumer)),
+            '-' => Ok(self.build_reserved_token_using_lookahead('=', TokenType::MinusEqual, TokenType::Minus, consumer)),
+            '+' if consumer.match_char('+') => Ok(self.build_reserved_token(TokenType::PlusPlus, consumer)),
+            '+' => Ok(self.build_reserved_token_using_lookahead('=', TokenType::PlusEqual, TokenType::Plus, consumer)),
             ';' => Ok(self.build_reserved_token(TokenType::Semicolon, consumer)),
-            '*' => Ok(self.build_reserved_token(TokenType::Star, consumer)),
+            '%' => Ok(self.build_reserved_token(TokenType::Percent, consumer)),
+            '*' if consumer.match_char('=') => Ok(self.build_reserved_token(TokenType::StarEqual, consumer)),
+            '*' => Ok(self.build_reserved_token_using_lookahead('*', TokenType::StarStar, TokenType::Star, consumer)),
             '!' => Ok(self.build_reserved_token_using_lookahead('=', TokenType::BangEqual, TokenType::Bang, consumer)),
             '=' => Ok(self.build_reserved_token_using_lookahead('=', TokenType::EqualEqual, TokenType::Equal, consumer)),
             '<' => Ok(self.build_reserved_token_using_lookahead('=', TokenType::LessEqual, TokenType::Less, consumer)),
             '>' => Ok(self.build_reserved_token_using_lookahead('=', TokenType::GreaterEqual, TokenType::Greater, consumer)),
-            '/' if consumer.match_char('/') => Ok(self.build_comment_token(consumer)),
-            '/' => Ok(self.build_reserved_token(TokenType::Slash, consumer)),
+            '/' if self.comment_style == CommentStyle::Slashes && consumer.match_char('/') => {
+                Ok(self.build_comment_token(consumer))
+            },
+            '/' if consumer.match_char('=') => Ok(self.build_reserved_token(TokenType::SlashEqual, consumer)),
+            '/' => Ok(self.build_reserved_token_using_lookahead('/', TokenType::SlashSlash, TokenType::Slash, consumer)),
+            '#' if self.comment_style == CommentStyle::Hash => Ok(self.build_comment_token(consumer)),
             ' ' | '\r' | '\t' => Ok(self.build_reserved_token(TokenType::Whitespace, consumer)),
             '\n' => Ok(self.build_reserved_token(TokenType::EndOfLine, consumer)),
-            '"' => Ok(self.build_string_literal_token(consumer)?),
+            '"' => self.build_string_literal_token(consumer),
             '0' ..= '9' => Ok(self.build_numeric_literal_token(consumer)),
             'a' ..= 'z' | 'A' ..= 'Z' | '_' => Ok(self.build_keyword_or_identifier_token(consumer)),
-            _ => Err(self.build_error(current_char, consumer))
+            _ => Err(vec![self.build_error(current_char, consumer)])
         }
     }
 
     pub fn scan_tokens(&mut self) -> Result<Vec<Token>, ScanningErrorSummary> {
+        self.scan_tokens_from_line(1)
+    }
+
+    fn scan_tokens_from_line(&mut self, line: u16) -> Result<Vec<Token>, ScanningErrorSummary> {
         let mut tokens: Vec<Token> = Vec::new();
         let mut errors: Vec<ScanningError> = Vec::new();
         let mut consumer = Consumer::new(&self.source);
+        consumer.current_line = line;
 
         while !consumer.is_at_end_of_input() {
             consumer.start_car = consumer.current_char;
@@ -279,7 +495,7 @@ impl Scanner {
                     TokenType::EndOfLine => consumer.current_line += 1,
                     _ => tokens.push(token)
                 },
-                Err(error) => errors.push(error)
+                Err(scanned_errors) => errors.extend(scanned_errors)
             }
         }
 
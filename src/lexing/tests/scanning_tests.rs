@@ -1,5 +1,5 @@
-use crate::lexing::scanner::{Scanner, ScanningError};
-use crate::lexing::token::{Token, TokenData, TokenType};
+use crate::lexing::scanner::{CommentStyle, Scanner, ScanningError};
+use crate::lexing::token::{StringPart, Token, TokenData, TokenType};
 
 #[test]
 fn should_return_eof_token_when_input_is_empty() {
@@ -115,6 +115,149 @@ fn should_return_unterminated_string_error_when_closing_quote_is_missing() {
     };
 }
 
+#[test]
+fn should_split_interpolated_string_into_text_and_token_runs() {
+    let mut scanner = Scanner::new(String::from("\"a ${f(\"}\", {\"k\": 1})} b\""));
+
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let TokenData::InterpolatedString { ref parts, .. } = tokens.first().unwrap().token_data
+    else { panic!("Token should be InterpolatedString")};
+
+    assert_eq!(parts.len(), 3);
+    assert!(matches!(&parts[0], StringPart::Text(text) if text == "a "));
+    assert!(matches!(&parts[2], StringPart::Text(text) if text == " b"));
+
+    let StringPart::Tokens(ref embedded) = parts[1] else { panic!("Part should be Tokens")};
+
+    assert_eq!(
+        get_token_types_from_tokens(embedded),
+        vec![TokenType::Identifier, TokenType::LeftParen, TokenType::String, TokenType::Comma,
+             TokenType::LeftBrace, TokenType::String, TokenType::Colon, TokenType::Number,
+             TokenType::RightBrace, TokenType::RightParen, TokenType::Eof]);
+}
+
+#[test]
+fn should_return_unterminated_interpolation_error_with_starting_line() {
+    let mut scanner = Scanner::new(String::from("\n\"value: ${1 + \n2"));
+    let errors = scanner.scan_tokens().err().unwrap().errors;
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], ScanningError::UnterminatedInterpolation { line: 2 }));
+}
+
+#[test]
+fn should_return_every_error_inside_interpolation() {
+    let mut scanner = Scanner::new(String::from("\"${@ + #}\" @"));
+    let errors = scanner.scan_tokens().err().unwrap().errors;
+
+    assert_eq!(errors.len(), 3);
+    assert!(matches!(errors[0], ScanningError::UnexpectedCharacter { line: 1, character: '@' }));
+    assert!(matches!(errors[1], ScanningError::UnexpectedCharacter { line: 1, character: '#' }));
+    assert!(matches!(errors[2], ScanningError::UnexpectedCharacter { line: 1, character: '@' }));
+}
+
+#[test]
+fn should_report_unterminated_string_after_invalid_escape() {
+    let mut scanner = Scanner::new(String::from("\"a\\q\nb"));
+    let errors = scanner.scan_tokens().err().unwrap().errors;
+
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(errors[0], ScanningError::InvalidEscape { line: 1, .. }));
+    assert!(matches!(errors[1], ScanningError::UnterminatedString { line: 2, .. }));
+}
+
+#[test]
+fn should_decode_escape_sequences_but_keep_original_lexeme() {
+    let input = "\"a\\n\\t\\r\\\\\\\"\\0\\$\\u{1F600}\"";
+    let mut scanner = Scanner::new(String::from(input));
+
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let TokenData::StringLiteral { ref lexeme, ref literal } = tokens.first().unwrap().token_data
+    else { panic!("Token should be StringLiteral")};
+
+    assert_eq!(lexeme, input);
+    assert_eq!(literal, "a\n\t\r\\\"\0$\u{1F600}");
+}
+
+#[test]
+fn should_display_decoded_string_literal_on_one_line() {
+    let cases = [
+        ("\"x\\ny\"", "STRING \"x\\ny\" x\\ny"),
+        ("\"q\\\"t\\u{1}\"", "STRING \"q\\\"t\\u{1}\" q\"t\\u{1}"),
+        ("\"a${1 + f(\"s\")}b\\n\\t\"", "STRING \"a${1 + f(\"s\")}b\\n\\t\" a${1 + f ( \"s\" )}b\\n\\t"),
+        ("\"\\\"${x}\\${y}\"", "STRING \"\\\"${x}\\${y}\" \"${x}\\${y}")
+    ];
+
+    for (input, expected_output) in cases {
+        let mut scanner = Scanner::new(String::from(input));
+        let tokens = scanner.scan_tokens().unwrap();
+
+        assert_eq!(format!("{}", tokens.first().unwrap()), expected_output);
+    }
+}
+
+#[test]
+fn should_return_invalid_escape_error_with_line_and_sequence() {
+    for (input, expected_sequence) in [("\n\"\\q\"", "\\q"), ("\n\"\\u{110000}\"", "\\u{110000}"), ("\n\"\\u41\"", "\\u")] {
+        let mut scanner = Scanner::new(String::from(input));
+        let errors = scanner.scan_tokens().err().unwrap().errors;
+
+        assert_eq!(errors.len(), 1);
+
+        match &errors[0] {
+            ScanningError::InvalidEscape { line, sequence } => {
+                assert_eq!(*line, 2);
+                assert_eq!(sequence, expected_sequence);
+            },
+            _ => panic!("Error should be InvalidEscape")
+        };
+    }
+}
+
+#[test]
+fn should_handle_arithmetic_operator_tokens() {
+    assert_eq!(
+        get_token_types_from_input("* ** / % //"),
+        vec![TokenType::Star, TokenType::StarStar, TokenType::Slash, TokenType::Percent, TokenType::Eof]);
+}
+
+#[test]
+fn should_handle_compound_assignment_and_update_tokens() {
+    assert_eq!(
+        get_token_types_from_input("+= -= *= /= ++ -- + -"),
+        vec![TokenType::PlusEqual, TokenType::MinusEqual, TokenType::StarEqual, TokenType::SlashEqual,
+             TokenType::PlusPlus, TokenType::MinusMinus, TokenType::Plus, TokenType::Minus, TokenType::Eof]);
+}
+
+#[test]
+fn should_handle_conditional_tokens() {
+    assert_eq!(
+        get_token_types_from_input("a ? b : c"),
+        vec![TokenType::Identifier, TokenType::Question, TokenType::Identifier,
+             TokenType::Colon, TokenType::Identifier, TokenType::Eof]);
+}
+
+#[test]
+fn should_scan_floor_division_when_comments_use_hash() {
+    let tokens = Scanner::new(String::from("7 // 2 # comment // ignored\n"))
+        .with_comment_style(CommentStyle::Hash)
+        .scan_tokens()
+        .unwrap();
+
+    let token_types: Vec<TokenType> = tokens.iter().map(|token| token.token_type).collect();
+
+    assert_eq!(token_types, vec![TokenType::Number, TokenType::SlashSlash, TokenType::Number, TokenType::Eof]);
+}
+
+#[test]
+fn should_return_error_for_hash_when_comments_use_slashes() {
+    let errors = Scanner::new(String::from("# comment")).scan_tokens().err().unwrap().errors;
+
+    assert!(matches!(errors[0], ScanningError::UnexpectedCharacter { line: 1, character: '#' }));
+}
+
 #[test]
 fn should_handle_numeric_literal() {
     let mut scanner = Scanner::new(String::from("12.45"));
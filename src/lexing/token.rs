@@ -7,11 +7,17 @@ use crate::util::string_util;
 pub enum TokenType {
     LeftParen, RightParen,
     LeftBrace, RightBrace,
+    LeftBracket, RightBracket,
     Comma,
+    Colon,
+    Question,
     Dot,
-    Minus, Plus,
+    Minus, MinusEqual, MinusMinus,
+    Plus, PlusEqual, PlusPlus,
     Semicolon,
-    Star, Slash,
+    Star, StarEqual, StarStar,
+    Slash, SlashEqual, SlashSlash,
+    Percent,
     Bang, BangEqual,
     Equal, EqualEqual,
     Less, LessEqual,
@@ -23,6 +29,7 @@ pub enum TokenType {
     If, Else,
     True, False, Nil,
     For, While,
+    Break, Continue,
     Class, Fun, Var,
     This, Super,
     Return,
@@ -55,12 +62,24 @@ impl PartialEq for TokenType {
 
 impl Eq for TokenType {}
 
+//** STRING PART *******************************************************************************************************
+
+/// One piece of an interpolated string.  The text between interpolations is kept as is, while
+/// each `${...}` is scanned into its own run of tokens that ends with an `Eof` token, ready to
+/// be handed to a parser of its own.
+#[derive(Debug, Clone)]
+pub enum StringPart {
+    Text(String),
+    Tokens(Vec<Token>)
+}
+
 //** TOKEN DATA ENUM **************************************************************************************************
 
 #[derive(Debug, Clone)]
 pub enum TokenData {
     Reserved { lexeme: String },
     StringLiteral { lexeme: String, literal: String },
+    InterpolatedString { lexeme: String, parts: Vec<StringPart> },
     NumericLiteral { lexeme: String, literal: f64 },
     Terminal, Comment
 }
@@ -74,6 +93,10 @@ impl TokenData {
         TokenData::StringLiteral { lexeme: String::from(lexeme), literal: String::from(literal) }
     }
 
+    pub fn new_interpolated_string(lexeme: &str, parts: Vec<StringPart>) -> Self {
+        TokenData::InterpolatedString { lexeme: String::from(lexeme), parts }
+    }
+
     pub fn new_numeric_literal(lexeme: &str, literal: f64) -> Self {
         TokenData::NumericLiteral { lexeme: String::from(lexeme), literal }
     }
@@ -106,6 +129,7 @@ impl Token {
         match &self.token_data {
             TokenData::Reserved { lexeme } |
             TokenData::StringLiteral { lexeme, literal: _ } |
+            TokenData::InterpolatedString { lexeme, parts: _ } |
             TokenData::NumericLiteral { lexeme, literal: _} => lexeme.to_string(),
             TokenData::Terminal |
             TokenData::Comment => String::new()
@@ -117,9 +141,50 @@ impl fmt::Display for Token {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match &self.token_data {
             TokenData::Reserved { lexeme } => write!(f, "{} {} null", self.token_type, lexeme),
-            TokenData::StringLiteral { lexeme, literal } => write!(f, "{} {} {}", self.token_type, lexeme, literal),
+            TokenData::StringLiteral { lexeme, literal } => write!(f, "{} {} {}", self.token_type, lexeme, display_text(literal)),
+            TokenData::InterpolatedString { lexeme, parts } => {
+                write!(f, "{} {} {}", self.token_type, lexeme, display_parts(parts))
+            },
             TokenData::NumericLiteral { lexeme, literal } => write!(f, "{} {} {:?}", self.token_type, lexeme, literal),
             TokenData::Terminal | TokenData::Comment => write!(f, "{}  null", self.token_type)
         }
     }
-}
\ No newline at end of file
+}
+
+/// The literal column of `tokenize` output shows the decoded text of a string.  Control
+/// characters are written back as escape sequences so that every token stays on its own line.
+fn display_text(text: &str) -> String {
+    let mut displayed = String::new();
+
+    for character in text.chars() {
+        match character {
+            '\n' => displayed.push_str("\\n"),
+            '\t' => displayed.push_str("\\t"),
+            '\r' => displayed.push_str("\\r"),
+            '\0' => displayed.push_str("\\0"),
+            character if character.is_control() => displayed.push_str(&format!("\\u{{{:x}}}", character as u32)),
+            character => displayed.push(character)
+        }
+    }
+
+    displayed
+}
+
+/// An interpolated string shows its decoded text the same way, with each embedded expression
+/// written as `${...}` around its lexemes.  A `${` that was escaped in the source is shown as
+/// `\${` so it can't be mistaken for one.
+fn display_parts(parts: &[StringPart]) -> String {
+    parts.iter()
+        .map(|part| match part {
+            StringPart::Text(text) => display_text(text).replace("${", "\\${"),
+            StringPart::Tokens(tokens) => {
+                let lexemes: Vec<String> = tokens.iter()
+                    .filter(|token| token.token_type != TokenType::Eof)
+                    .map(|token| token.get_name())
+                    .collect();
+
+                format!("${{{}}}", lexemes.join(" "))
+            }
+        })
+        .collect()
+}
@@ -1,6 +1,8 @@
 extern crate exitcode;
+mod conformance;
 mod lexing;
 mod parsing;
+mod resolving;
 mod interpreting;
 mod util;
 
@@ -9,31 +11,48 @@ use std::fs;
 use std::str::FromStr;
 use std::{env, fmt};
 use std::process::{exit};
+use std::thread;
+use crate::conformance::run_test_suite;
+use crate::lexing::scanner::CommentStyle;
 use crate::lexing::tokenize_file;
 use crate::parsing::{build_abstract_syntax_tree};
 use crate::interpreting::{evaluate_ast, interpret_program};
 use crate::util::error_handling::{ExitCodeProvider, InterpreterError};
 
+const USAGE: &str = "Usage: codecrafters-interpreter <tokenize|parse|evaluate|run|test> <path> [--comments=slashes|hash]";
+
+/// Every Lox call takes several frames of the interpreter's own stack, so the interpreter runs
+/// on a thread with a stack that is large enough for the deepest recursion the evaluator allows.
+const STACK_SIZE: usize = 64 * 1024 * 1024;
+
 //** VALIDATION ERRORS *************************************************************************************************
 
 #[derive(Debug)]
 enum ValidationError {
-    ArgumentCount { expected: usize, actual: usize },
+    ArgumentCount { minimum: usize, maximum: usize, actual: usize },
     Command { provided_command: String },
-    Filename { provided_filename: String }
+    Directory { provided_directory: String },
+    Filename { provided_filename: String },
+    Flag { provided_flag: String }
 }
 
 impl fmt::Display for ValidationError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            ValidationError::ArgumentCount { expected, actual } =>
-                write!(f, "Expected {} arguments, but received {}.", expected, actual),
+            ValidationError::ArgumentCount { minimum, maximum, actual } =>
+                write!(f, "Expected {} or {} arguments, but received {}.\n{}", minimum, maximum, actual, USAGE),
 
             ValidationError::Command { provided_command } =>
                 write!(f, "Invalid command: {}", provided_command),
 
+            ValidationError::Directory { provided_directory } =>
+                write!(f, "Invalid directory: {}", provided_directory),
+
             ValidationError::Filename { provided_filename } =>
-                write!(f, "Invalid filename: {}", provided_filename)
+                write!(f, "Invalid filename: {}", provided_filename),
+
+            ValidationError::Flag { provided_flag } =>
+                write!(f, "Invalid flag: {}\n{}", provided_flag, USAGE)
         }
     }
 }
@@ -51,7 +70,9 @@ impl ExitCodeProvider for ValidationError {
         match self {
             ValidationError::ArgumentCount { .. } => exitcode::USAGE,
             ValidationError::Command { .. } => exitcode::USAGE,
-            ValidationError::Filename { .. } => exitcode::IOERR
+            ValidationError::Directory { .. } => exitcode::IOERR,
+            ValidationError::Filename { .. } => exitcode::IOERR,
+            ValidationError::Flag { .. } => exitcode::USAGE
         }
     }
 }
@@ -62,7 +83,8 @@ enum Command {
     Tokenize,
     Parse,
     Evaluate,
-    Run
+    Run,
+    Test
 }
 
 /// FromStr does not have a lifetime parameter.  As a result, it can only parse types that
@@ -78,6 +100,7 @@ impl FromStr for Command {
             "parse" => Ok(Command::Parse),
             "evaluate" => Ok(Command::Evaluate),
             "run" => Ok(Command::Run),
+            "test" => Ok(Command::Test),
             _ => Err(ValidationError::Command { provided_command: input.to_string()})
         }
     }
@@ -85,9 +108,11 @@ impl FromStr for Command {
 
 //** INPUT VALIDATION **************************************************************************************************
 
-fn validate_input(args: &[String]) -> Result<(Command, &String), ValidationError>{
-    if args.len() != 3 {
-        return Err(ValidationError::ArgumentCount { expected: 3, actual: args.len() });
+/// An optional flag after the filename picks the comment style.  `--comments=hash` makes `#`
+/// start a comment so that `//` can be used for floor division.
+fn validate_input(args: &[String]) -> Result<(Command, &String, CommentStyle), ValidationError>{
+    if args.len() != 3 && args.len() != 4 {
+        return Err(ValidationError::ArgumentCount { minimum: 3, maximum: 4, actual: args.len() });
     }
 
     // The '?' operator is interesting.  Unpacks the Result if Ok, otherwise it will return
@@ -95,16 +120,27 @@ fn validate_input(args: &[String]) -> Result<(Command, &String), ValidationError
     let command = Command::from_str(&args[1])?;
 
     // Nice that pattern matching is a bit more robust than it is in Java.  Closer to what you
-    // can do in F#.
+    // can do in F#.  The test command is the only one that works on a directory of files.
     let filename =
-        match fs::metadata(&args[2]) {
-            Ok(metadata) if metadata.is_file() => &args[2],
+        match (&command, fs::metadata(&args[2])) {
+            (Command::Test, Ok(metadata)) if metadata.is_dir() => &args[2],
+            (Command::Test, _) => return Err(ValidationError::Directory {
+                provided_directory: args[2].to_string()
+            }),
+            (_, Ok(metadata)) if metadata.is_file() => &args[2],
             _ => return Err(ValidationError::Filename {
                 provided_filename: args[2].to_string()
             })
         };
 
-    Ok((command, filename))
+    let comment_style = match args.get(3) {
+        Some(flag) => CommentStyle::from_flag(flag).ok_or(ValidationError::Flag {
+            provided_flag: flag.to_string()
+        })?,
+        None => CommentStyle::default()
+    };
+
+    Ok((command, filename, comment_style))
 }
 
 //** EXECUTION LOGIC ***************************************************************************************************
@@ -119,26 +155,40 @@ fn handle_error(error: &InterpreterError) {
     }
 }
 
-fn execute_command(command: Command, filename: &str) -> Result<String, InterpreterError> {
+fn execute_command(command: Command, filename: &str, comment_style: CommentStyle) -> Result<String, InterpreterError> {
     match command {
-        Command::Tokenize => tokenize_file(filename).inspect_err(|error| handle_error(error)),
-        Command::Parse => build_abstract_syntax_tree(filename),
-        Command::Evaluate => evaluate_ast(filename).inspect_err(|error| handle_error(error)),
-        Command::Run => interpret_program(filename).inspect_err(|error| handle_error(error))
+        Command::Tokenize => tokenize_file(filename, comment_style).inspect_err(|error| handle_error(error)),
+        Command::Parse => build_abstract_syntax_tree(filename, comment_style),
+        Command::Evaluate => evaluate_ast(filename, comment_style).inspect_err(|error| handle_error(error)),
+        Command::Run => interpret_program(filename, comment_style).inspect_err(|error| handle_error(error)),
+        Command::Test => run_test_suite(filename, comment_style).inspect_err(handle_error)
     }
 }
 
 fn run() -> Result<i32, InterpreterError> {
     let args: Vec<String> = env::args().collect();
-    let (command, filename) = validate_input(&args)?;
-    let result = execute_command(command, filename)?;
+    let (command, filename, comment_style) = validate_input(&args).inspect_err(|error| eprintln!("{}", error))?;
+
+    // Running a program prints as it goes and has nothing left to report at the end.  Printing
+    // its empty result would add a blank line that is not part of the program's output.
+    let prints_result = !matches!(command, Command::Run);
+    let result = execute_command(command, filename, comment_style)?;
+
+    if prints_result {
+        println!("{}", result);
+    }
 
-    println!("{}", result);
     Ok(exitcode::OK)
 }
 
 fn main() {
-    exit(run().unwrap_or_else(|error| {
-        error.exit_code
-    }))
+    let interpreter = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(run)
+        .expect("Failed to start the interpreter thread");
+
+    exit(match interpreter.join() {
+        Ok(result) => result.unwrap_or_else(|error| error.exit_code),
+        Err(_) => exitcode::SOFTWARE
+    })
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use std::fs;
-use crate::lexing::scanner::Scanner;
-use crate::parsing::parser::Parser;
+use crate::lexing::scanner::{CommentStyle, Scanner};
+use crate::parsing::parser::{Parser, ParsingError};
 use crate::util::error_handling::InterpreterError;
 
 pub mod parser;
@@ -9,17 +9,32 @@ pub mod consumer;
 pub mod expression;
 pub mod statement;
 
-pub fn build_abstract_syntax_tree(filename: &str) -> Result<String, InterpreterError> {
+pub fn build_abstract_syntax_tree(filename: &str, comment_style: CommentStyle) -> Result<String, InterpreterError> {
     let file_contents = fs::read_to_string(filename).unwrap_or_else(|_| {
         eprintln!("Failed to read file {}:  Defaulting to an empty string", filename);
         String::new()
     });
 
-    let mut scanner = Scanner::new(file_contents);
+    let mut scanner = Scanner::new(file_contents).with_comment_style(comment_style);
     let tokens = scanner.scan_tokens()?;
 
     let parser = Parser::new(tokens);
-    let ast = parser.parse_ast()?;
 
-    Ok(format!("{}", ast))
+    Ok(format_syntax_tree(&parser)?)
+}
+
+/// The input is treated as a single expression when possible.  Otherwise it is parsed as a
+/// program and each top level statement is printed on its own line.  When neither works, the
+/// error from parsing it as an expression is the one reported.
+pub fn format_syntax_tree(parser: &Parser) -> Result<String, ParsingError> {
+    match parser.parse_ast() {
+        Ok(ast) => Ok(format!("{}", ast)),
+        Err(error) => match parser.parse() {
+            Ok(statements) if !statements.is_empty() => {
+                let output: Vec<String> = statements.iter().map(|statement| statement.to_string()).collect();
+                Ok(output.join("\n"))
+            },
+            _ => Err(error)
+        }
+    }
 }
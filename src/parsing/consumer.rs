@@ -41,6 +41,13 @@ impl<'a> Consumer {
         self.peek().token_type == token_type
     }
 
+    /// Looks one token past the current one without consuming anything.
+    pub(crate) fn check_next(&self, token_type: TokenType) -> bool {
+        if self.is_at_end() { return false; }
+
+        self.tokens[(self.current_index + 1) as usize].token_type == token_type
+    }
+
     pub(crate) fn match_token_type(&mut self, token_types: Vec<TokenType>) -> bool {
         for token_type in token_types {
             if self.check(token_type) {
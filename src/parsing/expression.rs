@@ -1,15 +1,42 @@
+use std::cell::Cell;
 use std::fmt;
 use crate::lexing::token::Token;
 
 //** EXPRESSION ********************************************************************************************************
 
+/// Expressions that refer to a variable carry a `depth`.  It starts out empty and is filled in
+/// by the resolver with the number of scopes between the expression and the variable's
+/// declaration.  It stays empty for global variables.
+///
+/// `CompoundAssign` and `Update` keep their target as the `Variable`, `Get` or `Index`
+/// expression it was parsed as, so the target's subexpressions can be evaluated only once even
+/// though the target is both read and written.
 #[derive(Debug, Clone)]
 pub enum Expression {
     Binary { left: Box<Expression>, operator: Token, right: Box<Expression> },
     Unary { operator: Token, right: Box<Expression> },
     StringLiteral { value: String },
     NumericLiteral { value: f64 },
-    Grouping { expression: Box<Expression> }
+    BooleanLiteral { value: bool },
+    NilLiteral,
+    Interpolation { parts: Vec<Expression> },
+    Grouping { expression: Box<Expression> },
+    Variable { name: Token, depth: Cell<Option<usize>> },
+    Assign { name: Token, value: Box<Expression>, depth: Cell<Option<usize>> },
+    CompoundAssign { target: Box<Expression>, operator: Token, value: Box<Expression> },
+    Update { target: Box<Expression>, operator: Token, prefix: bool },
+    Logical { left: Box<Expression>, operator: Token, right: Box<Expression> },
+    Conditional { condition: Box<Expression>, then_branch: Box<Expression>, else_branch: Box<Expression> },
+    Call { callee: Box<Expression>, paren: Token, arguments: Vec<Expression> },
+    This { keyword: Token, depth: Cell<Option<usize>> },
+    Get { object: Box<Expression>, name: Token },
+    Set { object: Box<Expression>, name: Token, value: Box<Expression> },
+    Super { keyword: Token, method: Token, depth: Cell<Option<usize>> },
+    List { elements: Vec<Expression> },
+    Map { brace: Token, entries: Vec<(Expression, Expression)> },
+    Index { object: Box<Expression>, bracket: Token, index: Box<Expression> },
+    IndexSet { object: Box<Expression>, bracket: Token, index: Box<Expression>, value: Box<Expression> },
+    Slice { object: Box<Expression>, bracket: Token, start: Box<Expression>, end: Box<Expression> }
 }
 
 impl Expression {
@@ -29,10 +56,90 @@ impl Expression {
         Expression::NumericLiteral { value }
     }
 
+    pub(crate) fn boolean_literal_from(value: bool) -> Self {
+        Expression::BooleanLiteral { value }
+    }
+
+    pub(crate) fn nil_literal() -> Self {
+        Expression::NilLiteral
+    }
+
+    pub(crate) fn interpolation_from(parts: Vec<Expression>) -> Self {
+        Expression::Interpolation { parts }
+    }
+
     pub(crate) fn grouping_from(expression: Expression) -> Self {
         Expression::Grouping { expression: Box::from(expression.clone()) }
     }
 
+    pub(crate) fn variable_from(name: Token) -> Self {
+        Expression::Variable { name, depth: Cell::new(None) }
+    }
+
+    pub(crate) fn assign_from(name: Token, value: Expression) -> Self {
+        Expression::Assign { name, value: Box::from(value), depth: Cell::new(None) }
+    }
+
+    pub(crate) fn compound_assign_from(target: Expression, operator: Token, value: Expression) -> Self {
+        Expression::CompoundAssign { target: Box::from(target), operator, value: Box::from(value) }
+    }
+
+    pub(crate) fn update_from(target: Expression, operator: Token, prefix: bool) -> Self {
+        Expression::Update { target: Box::from(target), operator, prefix }
+    }
+
+    pub(crate) fn logical_from(left: Expression, operator: Token, right: Expression) -> Self {
+        Expression::Logical { left: Box::from(left), operator, right: Box::from(right) }
+    }
+
+    pub(crate) fn conditional_from(condition: Expression, then_branch: Expression, else_branch: Expression) -> Self {
+        Expression::Conditional {
+            condition: Box::from(condition),
+            then_branch: Box::from(then_branch),
+            else_branch: Box::from(else_branch)
+        }
+    }
+
+    pub(crate) fn call_from(callee: Expression, paren: Token, arguments: Vec<Expression>) -> Self {
+        Expression::Call { callee: Box::from(callee), paren, arguments }
+    }
+
+    pub(crate) fn this_from(keyword: Token) -> Self {
+        Expression::This { keyword, depth: Cell::new(None) }
+    }
+
+    pub(crate) fn get_from(object: Expression, name: Token) -> Self {
+        Expression::Get { object: Box::from(object), name }
+    }
+
+    pub(crate) fn set_from(object: Expression, name: Token, value: Expression) -> Self {
+        Expression::Set { object: Box::from(object), name, value: Box::from(value) }
+    }
+
+    pub(crate) fn super_from(keyword: Token, method: Token) -> Self {
+        Expression::Super { keyword, method, depth: Cell::new(None) }
+    }
+
+    pub(crate) fn list_from(elements: Vec<Expression>) -> Self {
+        Expression::List { elements }
+    }
+
+    pub(crate) fn map_from(brace: Token, entries: Vec<(Expression, Expression)>) -> Self {
+        Expression::Map { brace, entries }
+    }
+
+    pub(crate) fn index_from(object: Expression, bracket: Token, index: Expression) -> Self {
+        Expression::Index { object: Box::from(object), bracket, index: Box::from(index) }
+    }
+
+    pub(crate) fn index_set_from(object: Expression, bracket: Token, index: Expression, value: Expression) -> Self {
+        Expression::IndexSet { object: Box::from(object), bracket, index: Box::from(index), value: Box::from(value) }
+    }
+
+    pub(crate) fn slice_from(object: Expression, bracket: Token, start: Expression, end: Expression) -> Self {
+        Expression::Slice { object: Box::from(object), bracket, start: Box::from(start), end: Box::from(end) }
+    }
+
     fn parenthesize(name: &str, expressions: Vec<&Expression>) -> String {
         let mut output = String::new();
 
@@ -52,7 +159,8 @@ impl Expression {
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Expression::Binary { left, operator, right } => {
+            Expression::Binary { left, operator, right } |
+            Expression::Logical { left, operator, right } => {
                 write!(f, "{}", Expression::parenthesize(&operator.get_name(), vec![left, right]))
             },
             Expression::Unary { operator, right } => {
@@ -60,8 +168,58 @@ impl fmt::Display for Expression {
             },
             Expression::StringLiteral { value } => write!(f, "{}", value),
             Expression::NumericLiteral { value } => write!(f, "{:?}", value),
+            Expression::BooleanLiteral { value } => write!(f, "{}", value),
+            Expression::NilLiteral => write!(f, "nil"),
+            Expression::Interpolation { parts } => {
+                write!(f, "{}", Expression::parenthesize("interpolate", parts.iter().collect()))
+            },
             Expression::Grouping { expression } => {
                 write!(f, "{}", Expression::parenthesize("group", vec![expression]))
+            },
+            Expression::Variable { name, depth: _ } => write!(f, "{}", name.get_name()),
+            Expression::Assign { name, value, depth: _ } => {
+                write!(f, "{}", Expression::parenthesize(&format!("= {}", name.get_name()), vec![value]))
+            },
+            Expression::CompoundAssign { target, operator, value } => {
+                write!(f, "{}", Expression::parenthesize(&operator.get_name(), vec![target, value]))
+            },
+            Expression::Update { target, operator, prefix } => {
+                let position = if *prefix { "pre" } else { "post" };
+
+                write!(f, "{}", Expression::parenthesize(&format!("{}{}", position, operator.get_name()), vec![target]))
+            },
+            Expression::Conditional { condition, then_branch, else_branch } => {
+                write!(f, "{}", Expression::parenthesize("?", vec![condition, then_branch, else_branch]))
+            },
+            Expression::Call { callee, paren: _, arguments } => {
+                write!(f, "{}", Expression::parenthesize(&format!("call {}", callee), arguments.iter().collect()))
+            },
+            Expression::This { keyword, depth: _ } => write!(f, "{}", keyword.get_name()),
+            Expression::Get { object, name } => {
+                write!(f, "{}", Expression::parenthesize(&format!(". {}", name.get_name()), vec![object]))
+            },
+            Expression::Set { object, name, value } => {
+                write!(f, "{}", Expression::parenthesize(&format!("= {}", name.get_name()), vec![object, value]))
+            },
+            Expression::Super { keyword, method, depth: _ } => {
+                write!(f, "(. {} {})", method.get_name(), keyword.get_name())
+            },
+            Expression::List { elements } => {
+                write!(f, "{}", Expression::parenthesize("list", elements.iter().collect()))
+            },
+            Expression::Map { brace: _, entries } => {
+                let entries: String = entries.iter().map(|(key, value)| format!(" ({} {})", key, value)).collect();
+
+                write!(f, "(map{})", entries)
+            },
+            Expression::Index { object, bracket: _, index } => {
+                write!(f, "{}", Expression::parenthesize("index", vec![object, index]))
+            },
+            Expression::IndexSet { object, bracket: _, index, value } => {
+                write!(f, "(= {} {})", Expression::parenthesize("index", vec![object, index]), value)
+            },
+            Expression::Slice { object, bracket: _, start, end } => {
+                write!(f, "{}", Expression::parenthesize("slice", vec![object, start, end]))
             }
         }
     }
@@ -1,11 +1,13 @@
-use crate::lexing::token::{Token, TokenData, TokenType};
+use crate::lexing::token::{StringPart, Token, TokenData, TokenType};
 use exitcode::ExitCode;
 use std::{fmt};
+use std::cell::RefCell;
+use std::rc::Rc;
 use crate::lexing::token::TokenType::Semicolon;
 use crate::parsing::consumer::Consumer;
 use crate::parsing::expression::Expression;
-use crate::parsing::statement::Statement;
-use crate::parsing::statement::Statement::{ExpressionStmt, PrintStmt};
+use crate::parsing::statement::{FunctionDeclaration, Statement};
+use crate::parsing::statement::Statement::{BlockStmt, BreakStmt, ClassStmt, ContinueStmt, ExpressionStmt, FunctionStmt, IfStmt, PrintStmt, ReturnStmt, VarStmt, WhileStmt};
 use crate::util::error_handling::{ExitCodeProvider};
 
 //** PARSING ERRORS ****************************************************************************************************
@@ -13,14 +15,28 @@ use crate::util::error_handling::{ExitCodeProvider};
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub enum ParsingError {
+    ExpectedConditionalColon,
     ExpectedExpression,
+    ExpectedLoopAfterLabel { label: String },
+    InvalidAssignmentTarget,
+    LoopControlOutsideLoop { keyword: String },
+    TooManyArguments,
+    TooManyParameters,
+    UndefinedLabel { label: String },
     UnexpectedToken
 }
 
 impl fmt::Display for ParsingError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            ParsingError::ExpectedConditionalColon => write!(f, "Expect ':' after then branch of conditional expression."),
             ParsingError::ExpectedExpression => write!(f, "Expected expression."),
+            ParsingError::ExpectedLoopAfterLabel { label } => write!(f, "Expect loop after label '{}'.", label),
+            ParsingError::InvalidAssignmentTarget => write!(f, "Invalid assignment target."),
+            ParsingError::LoopControlOutsideLoop { keyword } => write!(f, "Can't use '{}' outside of a loop.", keyword),
+            ParsingError::TooManyArguments => write!(f, "Can't have more than {} arguments.", MAX_ARGUMENTS),
+            ParsingError::TooManyParameters => write!(f, "Can't have more than {} parameters.", MAX_ARGUMENTS),
+            ParsingError::UndefinedLabel { label } => write!(f, "No enclosing loop is labelled '{}'.", label),
             ParsingError::UnexpectedToken => write!(f, "Unexpected token.")
         }
     }
@@ -36,7 +52,14 @@ impl ExitCodeProvider for ParsingError {
     }
     fn get_exit_code(&self) -> ExitCode {
         match self {
+            ParsingError::ExpectedConditionalColon => ExitCode::from(65),
             ParsingError::ExpectedExpression => ExitCode::from(65),
+            ParsingError::ExpectedLoopAfterLabel { .. } => ExitCode::from(65),
+            ParsingError::InvalidAssignmentTarget => ExitCode::from(65),
+            ParsingError::LoopControlOutsideLoop { .. } => ExitCode::from(65),
+            ParsingError::TooManyArguments => ExitCode::from(65),
+            ParsingError::TooManyParameters => ExitCode::from(65),
+            ParsingError::UndefinedLabel { .. } => ExitCode::from(65),
             ParsingError::UnexpectedToken => ExitCode::from(65)
         }
     }
@@ -44,32 +67,68 @@ impl ExitCodeProvider for ParsingError {
 
 //** PARSER ************************************************************************************************************
 
+/// The maximum number of arguments that can be passed to a function.  This also limits the
+/// number of parameters a function can declare.
+const MAX_ARGUMENTS: usize = 255;
+
+/// The parser keeps track of the loops that enclose the statement it is parsing, innermost
+/// last, so that `break` and `continue` can be rejected when there is no loop for them to
+/// leave.  Each entry holds the loop's label, if it has one.
 pub struct Parser {
-    pub tokens: Vec<Token>
+    pub tokens: Vec<Token>,
+    enclosing_loops: RefCell<Vec<Option<String>>>
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
         Parser {
-            tokens
+            tokens,
+            enclosing_loops: RefCell::new(Vec::new())
         }
     }
 
     fn primary(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
-        if consumer.match_token_type(vec![TokenType::False]) { return Ok(Expression::string_literal_from("false")) };
-        if consumer.match_token_type(vec![TokenType::True]) { return Ok(Expression::string_literal_from("true")) };
-        if consumer.match_token_type(vec![TokenType::Nil]) { return Ok(Expression::string_literal_from("nil")) };
+        if consumer.match_token_type(vec![TokenType::False]) { return Ok(Expression::boolean_literal_from(false)) };
+        if consumer.match_token_type(vec![TokenType::True]) { return Ok(Expression::boolean_literal_from(true)) };
+        if consumer.match_token_type(vec![TokenType::Nil]) { return Ok(Expression::nil_literal()) };
 
         if consumer.match_token_type(vec![TokenType::Number, TokenType::String]) {
             let previous_token = &self.tokens[(consumer.current_index-1) as usize];
 
             return match &previous_token.token_data {
                 TokenData::StringLiteral { lexeme: _, literal } => Ok(Expression::string_literal_from(literal)),
+                TokenData::InterpolatedString { lexeme: _, parts } => self.interpolation(parts),
                 TokenData::NumericLiteral { lexeme: _, literal } => Ok(Expression::numeric_literal_from(literal.clone())),
                 _ => panic!("adf")
             };
         }
 
+        if consumer.match_token_type(vec![TokenType::Super]) {
+            let keyword = consumer.previous().clone();
+            consumer.consume(TokenType::Dot, "Expect '.' after 'super'.")?;
+            let method = consumer.consume(TokenType::Identifier, "Expect superclass method name.")?.clone();
+
+            return Ok(Expression::super_from(keyword, method));
+        }
+
+        if consumer.match_token_type(vec![TokenType::This]) {
+            return Ok(Expression::this_from(consumer.previous().clone()));
+        }
+
+        if consumer.match_token_type(vec![TokenType::Identifier]) {
+            return Ok(Expression::variable_from(consumer.previous().clone()));
+        }
+
+        if consumer.match_token_type(vec![TokenType::LeftBracket]) {
+            return self.finish_list(consumer);
+        }
+
+        // A brace at the start of a statement has already been taken as a block, so one that
+        // reaches this point is in an expression and can only start a map.
+        if consumer.match_token_type(vec![TokenType::LeftBrace]) {
+            return self.finish_map(consumer);
+        }
+
         if consumer.match_token_type(vec![TokenType::LeftParen]) {
             let expression = self.expression(consumer)?;
 
@@ -86,6 +145,22 @@ impl Parser {
         Err(ParsingError::ExpectedExpression)
     }
 
+    /// Each embedded expression was scanned into its own run of tokens, so it is parsed by a
+    /// parser of its own.  The run has to hold exactly one expression.
+    fn interpolation(&self, parts: &[StringPart]) -> Result<Expression, ParsingError> {
+        let mut expressions = Vec::new();
+
+        for part in parts {
+            match part {
+                StringPart::Text(text) if text.is_empty() => {},
+                StringPart::Text(text) => expressions.push(Expression::string_literal_from(text)),
+                StringPart::Tokens(tokens) => expressions.push(Parser::new(tokens.clone()).parse_ast()?)
+            }
+        }
+
+        Ok(Expression::interpolation_from(expressions))
+    }
+
     fn unary(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
         if consumer.match_token_type(vec![TokenType::Bang, TokenType::Minus]) {
             let operator = &self.tokens[(consumer.current_index-1) as usize];
@@ -94,13 +169,180 @@ impl Parser {
             return Ok(Expression::unary_from(operator.clone(), right));
         }
 
-        self.primary(consumer)
+        self.power(consumer)
+    }
+
+    /// `**` binds tighter than the unary operators, so `-2 ** 2` is `-(2 ** 2)`.  Its right
+    /// operand is parsed as a unary expression, which both allows `2 ** -1` and makes the
+    /// operator right-associative: `2 ** 3 ** 2` is `2 ** (3 ** 2)`.
+    fn power(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        let expression = self.update(consumer)?;
+
+        if consumer.match_token_type(vec![TokenType::StarStar]) {
+            let operator = &self.tokens[(consumer.current_index-1) as usize];
+            let right = self.unary(consumer)?;
+
+            return Ok(Expression::binary_from(expression, operator.clone(), right));
+        }
+
+        Ok(expression)
+    }
+
+    /// Prefix and postfix `++` / `--` apply to a call level expression, so `-a++` is `-(a++)`
+    /// and `++a ** 2` is `(++a) ** 2`.
+    ///
+    /// Lox allows two minus signs in a row, as in `--(3)` or `1--1`, which scan as a single `--`.
+    /// When what follows a prefix `--` can't be assigned to, it is parsed again as two negations.
+    /// A `--` after an operand that can't be assigned to, or that is followed by another
+    /// operand, is left for `term` to read as a subtraction of a negated operand.
+    fn update(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        if consumer.match_token_type(vec![TokenType::PlusPlus, TokenType::MinusMinus]) {
+            let operator = self.tokens[(consumer.current_index-1) as usize].clone();
+            let start = consumer.current_index;
+
+            return match (self.call(consumer).map(Self::assignment_target), operator.token_type) {
+                (Ok(Ok(target)), _) => Ok(Expression::update_from(target, operator, true)),
+                (_, TokenType::MinusMinus) => {
+                    consumer.current_index = start;
+                    self.double_negation(&operator, consumer)
+                },
+                (Ok(Err(error)), _) | (Err(error), _) => Err(error)
+            };
+        }
+
+        let expression = self.call(consumer)?;
+
+        if consumer.check(TokenType::MinusMinus) && (!Self::is_assignable(&expression) || Self::operand_follows(consumer)) {
+            return Ok(expression);
+        }
+
+        if consumer.match_token_type(vec![TokenType::PlusPlus, TokenType::MinusMinus]) {
+            let operator = self.tokens[(consumer.current_index-1) as usize].clone();
+
+            return Ok(Expression::update_from(Self::assignment_target(expression)?, operator, false));
+        }
+
+        Ok(expression)
+    }
+
+    fn double_negation(&self, operator: &Token, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        let minus = Token::new(operator.line, TokenType::Minus, TokenData::new_reserved("-"));
+        let right = self.unary(consumer)?;
+
+        Ok(Expression::unary_from(minus.clone(), Expression::unary_from(minus, right)))
+    }
+
+    /// Whether the token after the current one can start an operand, as `b` does in `a--b`.
+    fn operand_follows(consumer: &Consumer) -> bool {
+        [TokenType::Number, TokenType::String, TokenType::Identifier, TokenType::This, TokenType::Super,
+            TokenType::True, TokenType::False, TokenType::Nil, TokenType::LeftParen, TokenType::LeftBracket,
+            TokenType::Bang]
+            .into_iter()
+            .any(|token_type| consumer.check_next(token_type))
+    }
+
+    fn finish_call(&self, callee: Expression, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        let mut arguments = Vec::new();
+
+        if !consumer.check(TokenType::RightParen) {
+            loop {
+                if arguments.len() >= MAX_ARGUMENTS { return Err(ParsingError::TooManyArguments); }
+
+                arguments.push(self.expression(consumer)?);
+
+                if !consumer.match_token_type(vec![TokenType::Comma]) { break; }
+            }
+        }
+
+        let paren = consumer.consume(TokenType::RightParen, "Expect ')' after arguments.")?.clone();
+
+        Ok(Expression::call_from(callee, paren, arguments))
+    }
+
+    fn finish_list(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        let mut elements = Vec::new();
+
+        if !consumer.check(TokenType::RightBracket) {
+            loop {
+                elements.push(self.expression(consumer)?);
+
+                if !consumer.match_token_type(vec![TokenType::Comma]) { break; }
+            }
+        }
+
+        consumer.consume(TokenType::RightBracket, "Expect ']' after list elements.")?;
+
+        Ok(Expression::list_from(elements))
+    }
+
+    fn finish_map(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        let brace = consumer.previous().clone();
+        let mut entries = Vec::new();
+
+        if !consumer.check(TokenType::RightBrace) {
+            loop {
+                let key = self.expression(consumer)?;
+                consumer.consume(TokenType::Colon, "Expect ':' after map key.")?;
+                let value = self.expression(consumer)?;
+
+                entries.push((key, value));
+
+                if !consumer.match_token_type(vec![TokenType::Comma]) { break; }
+            }
+        }
+
+        consumer.consume(TokenType::RightBrace, "Expect '}' after map entries.")?;
+
+        Ok(Expression::map_from(brace, entries))
+    }
+
+    /// Parses either `object[index]` or `object[start:end]`.  Either bound of a slice can be
+    /// left out, in which case it is filled in with `nil` and the evaluator uses the start or
+    /// the end of the list instead.
+    fn finish_index(&self, object: Expression, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        let bracket = consumer.previous().clone();
+
+        let start = if consumer.check(TokenType::Colon) { Expression::nil_literal() } else { self.expression(consumer)? };
+
+        if consumer.match_token_type(vec![TokenType::Colon]) {
+            let end = if consumer.check(TokenType::RightBracket) { Expression::nil_literal() } else { self.expression(consumer)? };
+            consumer.consume(TokenType::RightBracket, "Expect ']' after slice.")?;
+
+            return Ok(Expression::slice_from(object, bracket, start, end));
+        }
+
+        consumer.consume(TokenType::RightBracket, "Expect ']' after index.")?;
+
+        Ok(Expression::index_from(object, bracket, start))
+    }
+
+    fn call(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        let mut expression = self.primary(consumer)?;
+
+        loop {
+            if consumer.match_token_type(vec![TokenType::LeftParen]) {
+                expression = self.finish_call(expression, consumer)?;
+            } else if consumer.match_token_type(vec![TokenType::LeftBracket]) {
+                expression = self.finish_index(expression, consumer)?;
+            } else if consumer.match_token_type(vec![TokenType::Dot]) {
+                let name = consumer.consume(TokenType::Identifier, "Expect property name after '.'.")?.clone();
+                expression = Expression::get_from(expression, name);
+            } else {
+                break;
+            }
+        }
+
+        Ok(expression)
     }
 
     fn factor(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
-        let mut expression = self.unary(consumer)?;
+        let first = self.unary(consumer)?;
 
-        while consumer.match_token_type(vec![TokenType::Slash, TokenType::Star]) {
+        self.finish_factor(first, consumer)
+    }
+
+    fn finish_factor(&self, mut expression: Expression, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        while consumer.match_token_type(vec![TokenType::Slash, TokenType::SlashSlash, TokenType::Star, TokenType::Percent]) {
             let operator = &self.tokens[(consumer.current_index-1) as usize];
             let right = self.unary(consumer)?;
             expression = Expression::binary_from(expression, operator.clone(), right)
@@ -112,8 +354,19 @@ impl Parser {
     fn term(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
         let mut expression = self.factor(consumer)?;
 
-        while consumer.match_token_type(vec![TokenType::Minus, TokenType::Plus]) {
+        while consumer.match_token_type(vec![TokenType::Minus, TokenType::Plus, TokenType::MinusMinus]) {
             let operator = &self.tokens[(consumer.current_index-1) as usize];
+
+            if operator.token_type == TokenType::MinusMinus {
+                // `a--b` is `a - -b`, so the `--` is split into a subtraction and a negation of
+                // the first operand of the right-hand side.
+                let minus = Token::new(operator.line, TokenType::Minus, TokenData::new_reserved("-"));
+                let negated = Expression::unary_from(minus.clone(), self.unary(consumer)?);
+                let right = self.finish_factor(negated, consumer)?;
+                expression = Expression::binary_from(expression, minus, right);
+                continue;
+            }
+
             let right = self.factor(consumer)?;
             expression = Expression::binary_from(expression, operator.clone(), right)
         }
@@ -148,14 +401,119 @@ impl Parser {
         Ok(expression)
     }
 
+    fn and(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        let mut expression = self.equality(consumer)?;
+
+        while consumer.match_token_type(vec![TokenType::And]) {
+            let operator = consumer.previous().clone();
+            let right = self.equality(consumer)?;
+
+            expression = Expression::logical_from(expression, operator, right)
+        }
+
+        Ok(expression)
+    }
+
+    fn or(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        let mut expression = self.and(consumer)?;
+
+        while consumer.match_token_type(vec![TokenType::Or]) {
+            let operator = consumer.previous().clone();
+            let right = self.and(consumer)?;
+
+            expression = Expression::logical_from(expression, operator, right)
+        }
+
+        Ok(expression)
+    }
+
+    /// The then branch can be any expression, including another conditional or an assignment,
+    /// because it is closed off by the `:`.  The else branch is parsed as a conditional so that
+    /// `a ? b : c ? d : e` groups as `a ? b : (c ? d : e)`.
+    fn conditional(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        let condition = self.or(consumer)?;
+
+        if consumer.match_token_type(vec![TokenType::Question]) {
+            let then_branch = self.expression(consumer)?;
+
+            if !consumer.match_token_type(vec![TokenType::Colon]) {
+                return Err(ParsingError::ExpectedConditionalColon);
+            }
+
+            let else_branch = self.conditional(consumer)?;
+
+            return Ok(Expression::conditional_from(condition, then_branch, else_branch));
+        }
+
+        Ok(condition)
+    }
+
+    /// Assignment is right associative, so the value is parsed by recursively calling
+    /// `assignment`.  The left-hand side is parsed as a normal expression first and only then
+    /// checked to make sure it is something that can actually be assigned to.
+    fn assignment(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
+        let expression = self.conditional(consumer)?;
+
+        if consumer.match_token_type(vec![TokenType::Equal]) {
+            let value = self.assignment(consumer)?;
+
+            return match expression {
+                Expression::Variable { name, depth: _ } => Ok(Expression::assign_from(name, value)),
+                Expression::Get { object, name } => Ok(Expression::set_from(*object, name, value)),
+                Expression::Index { object, bracket, index } => Ok(Expression::index_set_from(*object, bracket, *index, value)),
+                _ => Err(ParsingError::InvalidAssignmentTarget)
+            };
+        }
+
+        if consumer.match_token_type(vec![TokenType::PlusEqual, TokenType::MinusEqual, TokenType::StarEqual, TokenType::SlashEqual]) {
+            let operator = self.tokens[(consumer.current_index-1) as usize].clone();
+            let value = self.assignment(consumer)?;
+
+            return Ok(Expression::compound_assign_from(Self::assignment_target(expression)?, operator, value));
+        }
+
+        Ok(expression)
+    }
+
+    /// Compound assignments and `++` / `--` can change the same things as `=`: variables,
+    /// fields and indexed elements.
+    fn assignment_target(expression: Expression) -> Result<Expression, ParsingError> {
+        if Self::is_assignable(&expression) { Ok(expression) } else { Err(ParsingError::InvalidAssignmentTarget) }
+    }
+
+    fn is_assignable(expression: &Expression) -> bool {
+        matches!(expression, Expression::Variable { .. } | Expression::Get { .. } | Expression::Index { .. })
+    }
+
+    /// Operators from the loosest binding to the tightest:
+    ///
+    /// | Level       | Operators               | Associativity |
+    /// |-------------|-------------------------|---------------|
+    /// | assignment  | `=` `+=` `-=` `*=` `/=` | right         |
+    /// | conditional | `? :`                   | right         |
+    /// | or          | `or`                    | left          |
+    /// | and         | `and`                   | left          |
+    /// | equality    | `==` `!=`               | left          |
+    /// | comparison  | `<` `<=` `>` `>=`       | left          |
+    /// | term        | `+` `-`                 | left          |
+    /// | factor      | `*` `/` `//` `%`        | left          |
+    /// | unary       | `!` `-`                 | right         |
+    /// | power       | `**`                    | right         |
+    /// | update      | `++` `--`               | none          |
+    /// | call        | `()` `[]` `.`           | left          |
     fn expression(&self, consumer: &mut Consumer) -> Result<Expression, ParsingError> {
-        self.equality(consumer)
+        self.assignment(consumer)
     }
 
     pub fn parse_ast(&self) -> Result<Expression, ParsingError> {
         let mut consumer = Consumer::new(&self.tokens);
+        let expression = self.expression(&mut consumer)?;
+
+        // Anything left over means the input was more than a single expression, for example a
+        // program made up of statements.
+        if !consumer.is_at_end() { return Err(ParsingError::UnexpectedToken); }
 
-        self.expression(&mut consumer)
+        Ok(expression)
     }
 
     fn print_statement(&self, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
@@ -165,6 +523,14 @@ impl Parser {
         Ok(PrintStmt { expression: value })
     }
 
+    fn return_statement(&self, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
+        let keyword = consumer.previous().clone();
+        let value = if !consumer.check(Semicolon) { Some(self.expression(consumer)?) } else { None };
+
+        consumer.consume(Semicolon, "Expect ';' after return value.")?;
+        Ok(ReturnStmt { keyword, value })
+    }
+
     fn expression_statement(&self, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
         let expression = self.expression(consumer)?;
 
@@ -172,20 +538,257 @@ impl Parser {
         Ok(ExpressionStmt { expression })
     }
 
+    /// The `else` is bound to the nearest `if` because the check for it happens right after the
+    /// then branch is parsed.  A nested `if` without braces will claim the `else` first.
+    fn if_statement(&self, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
+        consumer.consume(TokenType::LeftParen, "Expect '(' after 'if'.")?;
+        let condition = self.expression(consumer)?;
+        consumer.consume(TokenType::RightParen, "Expect ')' after if condition.")?;
+
+        let then_branch = Box::from(self.statement(consumer)?);
+        let else_branch = if consumer.match_token_type(vec![TokenType::Else]) {
+            Some(Box::from(self.statement(consumer)?))
+        } else {
+            None
+        };
+
+        Ok(IfStmt { condition, then_branch, else_branch })
+    }
+
+    /// The loop is only on the stack of enclosing loops while its body is being parsed.
+    fn loop_body(&self, label: &Option<Token>, consumer: &mut Consumer) -> Result<Box<Statement>, ParsingError> {
+        self.enclosing_loops.borrow_mut().push(label.as_ref().map(|label| label.get_name()));
+        let body = self.statement(consumer);
+        self.enclosing_loops.borrow_mut().pop();
+
+        Ok(Box::from(body?))
+    }
+
+    fn while_statement(&self, label: Option<Token>, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
+        consumer.consume(TokenType::LeftParen, "Expect '(' after 'while'.")?;
+        let condition = self.expression(consumer)?;
+        consumer.consume(TokenType::RightParen, "Expect ')' after condition.")?;
+
+        let body = self.loop_body(&label, consumer)?;
+
+        Ok(WhileStmt { condition, body, increment: None, label })
+    }
+
+    /// There is no dedicated statement for a `for` loop.  Instead, it is desugared into a
+    /// `while` loop wrapped in a block so that the initializer is scoped to the loop:
+    /// ```
+    /// for (var i = 0; i < 3; i = i + 1) print i;
+    /// { var i = 0; while (i < 3) { print i; i = i + 1; } }
+    /// ```
+    /// The increment is kept on the `while` loop rather than appended to the body.  That way it
+    /// still runs when a `continue` skips the rest of the body.
+    fn for_statement(&self, label: Option<Token>, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
+        consumer.consume(TokenType::LeftParen, "Expect '(' after 'for'.")?;
+
+        let initializer = if consumer.match_token_type(vec![Semicolon]) {
+            None
+        } else if consumer.match_token_type(vec![TokenType::Var]) {
+            Some(self.var_declaration(consumer)?)
+        } else {
+            Some(self.expression_statement(consumer)?)
+        };
+
+        let condition = if !consumer.check(Semicolon) { Some(self.expression(consumer)?) } else { None };
+        consumer.consume(Semicolon, "Expect ';' after loop condition.")?;
+
+        let increment = if !consumer.check(TokenType::RightParen) { Some(self.expression(consumer)?) } else { None };
+        consumer.consume(TokenType::RightParen, "Expect ')' after for clauses.")?;
+
+        let mut body = WhileStmt {
+            condition: condition.unwrap_or(Expression::boolean_literal_from(true)),
+            body: self.loop_body(&label, consumer)?,
+            increment,
+            label
+        };
+
+        if let Some(initializer) = initializer {
+            body = BlockStmt { statements: vec![initializer, body] };
+        }
+
+        Ok(body)
+    }
+
+    /// A label names the loop that immediately follows it, so that `break` and `continue` in a
+    /// nested loop can refer to it.
+    fn labelled_statement(&self, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
+        let label = consumer.advance().clone();
+        consumer.consume(TokenType::Colon, "Expect ':' after label.")?;
+
+        if consumer.match_token_type(vec![TokenType::For]) {
+            return self.for_statement(Some(label), consumer);
+        }
+
+        if consumer.match_token_type(vec![TokenType::While]) {
+            return self.while_statement(Some(label), consumer);
+        }
+
+        Err(ParsingError::ExpectedLoopAfterLabel { label: label.get_name() })
+    }
+
+    /// Handles both `break` and `continue`.  Without a label they apply to the innermost loop,
+    /// otherwise to the enclosing loop with a matching label.
+    fn loop_control_statement(&self, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
+        let keyword = consumer.previous().clone();
+        let label = if consumer.match_token_type(vec![TokenType::Identifier]) { Some(consumer.previous().clone()) } else { None };
+
+        consumer.consume(Semicolon, "Expect ';' after loop control statement.")?;
+
+        let enclosing_loops = self.enclosing_loops.borrow();
+
+        if enclosing_loops.is_empty() {
+            return Err(ParsingError::LoopControlOutsideLoop { keyword: keyword.get_name() });
+        }
+
+        if let Some(label) = &label {
+            if !enclosing_loops.iter().any(|enclosing| enclosing.as_ref() == Some(&label.get_name())) {
+                return Err(ParsingError::UndefinedLabel { label: label.get_name() });
+            }
+        }
+
+        match keyword.token_type {
+            TokenType::Break => Ok(BreakStmt { label }),
+            _ => Ok(ContinueStmt { label })
+        }
+    }
+
+    fn block(&self, consumer: &mut Consumer) -> Result<Vec<Statement>, ParsingError> {
+        let mut statements = Vec::new();
+
+        while !consumer.check(TokenType::RightBrace) && !consumer.is_at_end() {
+            statements.push(self.declaration(consumer)?);
+        }
+
+        consumer.consume(TokenType::RightBrace, "Expect '}' after block.")?;
+        Ok(statements)
+    }
+
     fn statement(&self, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
+        if consumer.check(TokenType::Identifier) && consumer.check_next(TokenType::Colon) {
+            return self.labelled_statement(consumer);
+        }
+
+        if consumer.match_token_type(vec![TokenType::Break, TokenType::Continue]) {
+            return self.loop_control_statement(consumer);
+        }
+
+        if consumer.match_token_type(vec![TokenType::For]) {
+            return self.for_statement(None, consumer);
+        }
+
+        if consumer.match_token_type(vec![TokenType::If]) {
+            return self.if_statement(consumer);
+        }
+
         if consumer.match_token_type(vec![TokenType::Print]) {
             return self.print_statement(consumer);
         }
 
+        if consumer.match_token_type(vec![TokenType::Return]) {
+            return self.return_statement(consumer);
+        }
+
+        if consumer.match_token_type(vec![TokenType::While]) {
+            return self.while_statement(None, consumer);
+        }
+
+        if consumer.match_token_type(vec![TokenType::LeftBrace]) {
+            return Ok(BlockStmt { statements: self.block(consumer)? });
+        }
+
         self.expression_statement(consumer)
     }
 
+    fn var_declaration(&self, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
+        let name = consumer.consume(TokenType::Identifier, "Expect variable name.")?.clone();
+
+        let initializer = if consumer.match_token_type(vec![TokenType::Equal]) {
+            Some(self.expression(consumer)?)
+        } else {
+            None
+        };
+
+        consumer.consume(Semicolon, "Expect ';' after variable declaration.")?;
+        Ok(VarStmt { name, initializer })
+    }
+
+    fn function(&self, consumer: &mut Consumer) -> Result<Rc<FunctionDeclaration>, ParsingError> {
+        let name = consumer.consume(TokenType::Identifier, "Expect function name.")?.clone();
+        consumer.consume(TokenType::LeftParen, "Expect '(' after function name.")?;
+
+        let mut params = Vec::new();
+
+        if !consumer.check(TokenType::RightParen) {
+            loop {
+                if params.len() >= MAX_ARGUMENTS { return Err(ParsingError::TooManyParameters); }
+
+                params.push(consumer.consume(TokenType::Identifier, "Expect parameter name.")?.clone());
+
+                if !consumer.match_token_type(vec![TokenType::Comma]) { break; }
+            }
+        }
+
+        consumer.consume(TokenType::RightParen, "Expect ')' after parameters.")?;
+        consumer.consume(TokenType::LeftBrace, "Expect '{' before function body.")?;
+
+        // A function body starts outside of any loop, even when the function is declared
+        // inside one.
+        let enclosing_loops = self.enclosing_loops.replace(Vec::new());
+        let body = self.block(consumer);
+        self.enclosing_loops.replace(enclosing_loops);
+        let body = body?;
+
+        Ok(Rc::new(FunctionDeclaration { name, params, body }))
+    }
+
+    fn class_declaration(&self, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
+        let name = consumer.consume(TokenType::Identifier, "Expect class name.")?.clone();
+
+        let superclass = if consumer.match_token_type(vec![TokenType::Less]) {
+            consumer.consume(TokenType::Identifier, "Expect superclass name.")?;
+            Some(Expression::variable_from(consumer.previous().clone()))
+        } else {
+            None
+        };
+
+        consumer.consume(TokenType::LeftBrace, "Expect '{' before class body.")?;
+
+        let mut methods = Vec::new();
+
+        while !consumer.check(TokenType::RightBrace) && !consumer.is_at_end() {
+            methods.push(self.function(consumer)?);
+        }
+
+        consumer.consume(TokenType::RightBrace, "Expect '}' after class body.")?;
+        Ok(ClassStmt { name, superclass, methods })
+    }
+
+    fn declaration(&self, consumer: &mut Consumer) -> Result<Statement, ParsingError> {
+        if consumer.match_token_type(vec![TokenType::Class]) {
+            return self.class_declaration(consumer);
+        }
+
+        if consumer.match_token_type(vec![TokenType::Fun]) {
+            return Ok(FunctionStmt { declaration: self.function(consumer)? });
+        }
+
+        if consumer.match_token_type(vec![TokenType::Var]) {
+            return self.var_declaration(consumer);
+        }
+
+        self.statement(consumer)
+    }
+
     pub fn parse(&self) -> Result<Vec<Statement>, ParsingError> {
         let mut consumer = Consumer::new(&self.tokens);
         let mut statements = Vec::new();
 
         while !consumer.is_at_end() {
-            statements.push(self.statement(&mut consumer)?);
+            statements.push(self.declaration(&mut consumer)?);
         }
 
         Ok(statements)
@@ -1,6 +1,124 @@
+use std::fmt;
+use std::rc::Rc;
+use crate::lexing::token::Token;
 use crate::parsing::expression::Expression;
 
+//** FUNCTION DECLARATION **********************************************************************************************
+
+/// Function declarations are shared between the statement that declares the function and every
+/// function value created from it, so the body is never copied when the function is defined.
+pub struct FunctionDeclaration {
+    pub name: Token,
+    pub params: Vec<Token>,
+    pub body: Vec<Statement>
+}
+
+//** STATEMENT *********************************************************************************************************
+
+#[allow(clippy::enum_variant_names)]
 pub enum Statement {
     PrintStmt { expression: Expression },
-    ExpressionStmt { expression: Expression }
-}
\ No newline at end of file
+    ExpressionStmt { expression: Expression },
+    VarStmt { name: Token, initializer: Option<Expression> },
+    BlockStmt { statements: Vec<Statement> },
+    IfStmt { condition: Expression, then_branch: Box<Statement>, else_branch: Option<Box<Statement>> },
+    WhileStmt { condition: Expression, body: Box<Statement>, increment: Option<Expression>, label: Option<Token> },
+    BreakStmt { label: Option<Token> },
+    ContinueStmt { label: Option<Token> },
+    FunctionStmt { declaration: Rc<FunctionDeclaration> },
+    ReturnStmt { keyword: Token, value: Option<Expression> },
+    ClassStmt { name: Token, superclass: Option<Expression>, methods: Vec<Rc<FunctionDeclaration>> }
+}
+
+impl Statement {
+    fn function_parts(declaration: &FunctionDeclaration) -> Vec<String> {
+        let params: Vec<String> = declaration.params.iter().map(|param| param.get_name()).collect();
+        let mut parts = vec![declaration.name.get_name(), format!("({})", params.join(" "))];
+        parts.extend(declaration.body.iter().map(|statement| statement.to_string()));
+
+        parts
+    }
+
+    fn parenthesize(name: &str, parts: Vec<String>) -> String {
+        let mut output = String::new();
+
+        output.push('(');
+        output.push_str(name);
+
+        for part in parts {
+            output.push(' ');
+            output.push_str(&part);
+        }
+
+        output.push(')');
+        output
+    }
+}
+
+/// Statements are displayed using the same S-expression style as expressions.  This is mostly
+/// useful for seeing how the parser desugared a construct, such as a `for` loop, into simpler
+/// statements.
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Statement::PrintStmt { expression } => {
+                write!(f, "{}", Statement::parenthesize("print", vec![expression.to_string()]))
+            },
+            Statement::ExpressionStmt { expression } => {
+                write!(f, "{}", Statement::parenthesize("expr", vec![expression.to_string()]))
+            },
+            Statement::VarStmt { name, initializer } => {
+                let mut parts = vec![name.get_name()];
+                parts.extend(initializer.iter().map(|expression| expression.to_string()));
+
+                write!(f, "{}", Statement::parenthesize("var", parts))
+            },
+            Statement::BlockStmt { statements } => {
+                write!(f, "{}", Statement::parenthesize("block", statements.iter().map(|s| s.to_string()).collect()))
+            },
+            Statement::IfStmt { condition, then_branch, else_branch } => {
+                let mut parts = vec![condition.to_string(), then_branch.to_string()];
+                parts.extend(else_branch.iter().map(|statement| statement.to_string()));
+
+                write!(f, "{}", Statement::parenthesize("if", parts))
+            },
+            Statement::WhileStmt { condition, body, increment, label } => {
+                // The increment of a `for` loop is shown where the desugaring puts it, at the end
+                // of the loop body.
+                let body = match increment {
+                    Some(increment) => Statement::parenthesize("block", vec![
+                        body.to_string(),
+                        Statement::parenthesize("expr", vec![increment.to_string()])
+                    ]),
+                    None => body.to_string()
+                };
+
+                let output = Statement::parenthesize("while", vec![condition.to_string(), body]);
+
+                match label {
+                    Some(label) => write!(f, "{}", Statement::parenthesize("label", vec![label.get_name(), output])),
+                    None => write!(f, "{}", output)
+                }
+            },
+            Statement::BreakStmt { label } => {
+                write!(f, "{}", Statement::parenthesize("break", label.iter().map(|label| label.get_name()).collect()))
+            },
+            Statement::ContinueStmt { label } => {
+                write!(f, "{}", Statement::parenthesize("continue", label.iter().map(|label| label.get_name()).collect()))
+            },
+            Statement::FunctionStmt { declaration } => {
+                write!(f, "{}", Statement::parenthesize("fun", Statement::function_parts(declaration)))
+            },
+            Statement::ReturnStmt { keyword: _, value } => {
+                write!(f, "{}", Statement::parenthesize("return", value.iter().map(|expression| expression.to_string()).collect()))
+            },
+            Statement::ClassStmt { name, superclass, methods } => {
+                let mut parts = vec![name.get_name()];
+                parts.extend(superclass.iter().map(|superclass| format!("(< {})", superclass)));
+                parts.extend(methods.iter().map(|method| Statement::parenthesize("fun", Statement::function_parts(method))));
+
+                write!(f, "{}", Statement::parenthesize("class", parts))
+            }
+        }
+    }
+}
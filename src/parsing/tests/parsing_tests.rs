@@ -1,6 +1,7 @@
-use crate::lexing::scanner::Scanner;
+use crate::lexing::scanner::{CommentStyle, Scanner};
 use crate::lexing::token::{Token, TokenData, TokenType};
-use crate::parsing::parser::{Parser};
+use crate::parsing::format_syntax_tree;
+use crate::parsing::parser::{Parser, ParsingError};
 use crate::parsing::expression::{Expression};
 
 #[test]
@@ -103,4 +104,412 @@ fn should_generate_the_expected_number_of_statements() {
     let result = &parser.parse().unwrap();
 
     assert_eq!(result.len(), 2);
-}
\ No newline at end of file
+}
+#[test]
+fn should_parse_variable_declarations() {
+    let input = "var a = 1; var b; print a;";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let result = &parser.parse().unwrap();
+
+    assert_eq!(result.len(), 3);
+}
+
+#[test]
+fn should_fail_to_parse_variable_declaration_without_name() {
+    let input = "var = 1;";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let result = &parser.parse();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn should_parse_assignment_as_right_associative() {
+    let input = "a = b = 3";
+    let expected_output = "(= a (= b 3.0))";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let ast = &parser.parse_ast().unwrap();
+
+    assert_eq!(format!("{}", ast), expected_output);
+}
+
+#[test]
+fn should_reject_invalid_assignment_target() {
+    let input = "1 + 2 = 3;";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let result = parser.parse();
+
+    assert!(matches!(result, Err(ParsingError::InvalidAssignmentTarget)));
+}
+
+#[test]
+fn should_parse_or_with_lower_precedence_than_and() {
+    let input = "a or b and c";
+    let expected_output = "(or a (and b c))";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let ast = &parser.parse_ast().unwrap();
+
+    assert_eq!(format!("{}", ast), expected_output);
+}
+
+#[test]
+fn should_desugar_for_loop_into_while_loop() {
+    let input = "for (var i = 0; i < 3; i = i + 1) print i;";
+    let expected_output = "(block (var i 0.0) (while (< i 3.0) (block (print i) (expr (= i (+ i 1.0))))))";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!(format!("{}", statements[0]), expected_output);
+}
+
+#[test]
+fn should_desugar_for_loop_without_clauses_into_infinite_loop() {
+    let input = "for (;;) print 1;";
+    let expected_output = "(while true (print 1.0))";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!(format!("{}", statements[0]), expected_output);
+}
+
+#[test]
+fn should_parse_chained_calls_with_arguments() {
+    let input = "f(1, a)()";
+    let expected_output = "(call (call f 1.0 a))";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let ast = &parser.parse_ast().unwrap();
+
+    assert_eq!(format!("{}", ast), expected_output);
+}
+
+#[test]
+fn should_reject_call_with_more_than_255_arguments() {
+    let arguments = vec!["1"; 256].join(", ");
+    let input = format!("f({});", arguments);
+
+    let mut scanner = Scanner::new(input);
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let result = parser.parse();
+
+    assert!(matches!(result, Err(ParsingError::TooManyArguments)));
+}
+
+#[test]
+fn should_parse_function_declaration() {
+    let input = "fun add(a, b) { print a + b; }";
+    let expected_output = "(fun add (a b) (print (+ a b)))";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!(format!("{}", statements[0]), expected_output);
+}
+
+#[test]
+fn should_parse_property_access_and_assignment() {
+    let input = "a.b.c = d.e";
+    let expected_output = "(= c (. b a) (. e d))";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let ast = &parser.parse_ast().unwrap();
+
+    assert_eq!(format!("{}", ast), expected_output);
+}
+
+#[test]
+fn should_parse_class_declaration_with_methods() {
+    let input = "class Greeter { greet(name) { print name; } }";
+    let expected_output = "(class Greeter (fun greet (name) (print name)))";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!(format!("{}", statements[0]), expected_output);
+}
+
+#[test]
+fn should_display_boolean_and_nil_literals_unchanged() {
+    let input = "(true == false) != nil";
+    let expected_output = "(!= (group (== true false)) nil)";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let ast = &parser.parse_ast().unwrap();
+
+    assert_eq!(format!("{}", ast), expected_output);
+}
+
+#[test]
+fn should_display_labelled_loop_with_break_and_continue() {
+    let input = "outer: while (true) { while (false) { break outer; } continue; }";
+    let expected_output = "(label outer (while true (block (while false (block (break outer))) (continue))))";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!(format!("{}", statements[0]), expected_output);
+}
+
+#[test]
+fn should_reject_break_outside_of_loop() {
+    for input in ["break;", "while (true) { fun f() { continue; } }", "a: while (true) { break b; }", "a: print 1;"] {
+        let mut scanner = Scanner::new(String::from(input));
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let parser = Parser::new(tokens);
+
+        assert!(parser.parse().is_err(), "{}", input);
+    }
+}
+
+#[test]
+fn should_parse_list_literal_index_and_slice() {
+    let input = "xs[0] = [1, 2][-1] + len(ys[:2])";
+    let expected_output = "(= (index xs 0.0) (+ (index (list 1.0 2.0) (- 1.0)) (call len (slice ys nil 2.0))))";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let ast = &parser.parse_ast().unwrap();
+
+    assert_eq!(format!("{}", ast), expected_output);
+}
+
+#[test]
+fn should_parse_brace_as_map_in_expression_and_block_in_statement() {
+    let input = "{ var m = {\"a\": 1, 2: {}}; }";
+    let expected_output = "(block (var m (map (a 1.0) (2.0 (map)))))";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!(format!("{}", statements[0]), expected_output);
+}
+
+#[test]
+fn should_parse_arithmetic_operators_with_precedence() {
+    let cases = [
+        ("2 ** 3 ** 2", "(** 2.0 (** 3.0 2.0))"),
+        ("-2 ** 2", "(- (** 2.0 2.0))"),
+        ("2 ** -1", "(** 2.0 (- 1.0))"),
+        ("a.b ** 2", "(** (. b a) 2.0)"),
+        ("1 + 7 % 3 * 2 ** 2", "(+ 1.0 (* (% 7.0 3.0) (** 2.0 2.0)))")
+    ];
+
+    for (input, expected_output) in cases {
+        let mut scanner = Scanner::new(String::from(input));
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let parser = Parser::new(tokens);
+        let ast = &parser.parse_ast().unwrap();
+
+        assert_eq!(format!("{}", ast), expected_output);
+    }
+}
+
+#[test]
+fn should_parse_floor_division_when_comments_use_hash() {
+    let input = "9 // 2 - 1 # half";
+    let expected_output = "(- (// 9.0 2.0) 1.0)";
+
+    let tokens = Scanner::new(String::from(input)).with_comment_style(CommentStyle::Hash).scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let ast = &parser.parse_ast().unwrap();
+
+    assert_eq!(format!("{}", ast), expected_output);
+}
+
+#[test]
+fn should_parse_compound_assignment_and_update_operators() {
+    let cases = [
+        ("a += b -= 2", "(+= a (-= b 2.0))"),
+        ("o.f *= 3", "(*= (. f o) 3.0)"),
+        ("l[0] /= 2", "(/= (index l 0.0) 2.0)"),
+        ("-a++", "(- (post++ a))"),
+        ("--o.f ** 2", "(** (pre-- (. f o)) 2.0)")
+    ];
+
+    for (input, expected_output) in cases {
+        let mut scanner = Scanner::new(String::from(input));
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let parser = Parser::new(tokens);
+        let ast = &parser.parse_ast().unwrap();
+
+        assert_eq!(format!("{}", ast), expected_output);
+    }
+}
+
+#[test]
+fn should_reject_invalid_update_targets() {
+    for input in ["1 += 2;", "a + b -= 1;", "f()++;", "++1;"] {
+        let mut scanner = Scanner::new(String::from(input));
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let parser = Parser::new(tokens);
+        let result = parser.parse();
+
+        assert!(matches!(result, Err(ParsingError::InvalidAssignmentTarget)));
+    }
+}
+
+#[test]
+fn should_parse_conditional_with_low_precedence_and_right_associativity() {
+    let cases = [
+        ("a or b ? 1 + 2 : 3", "(? (or a b) (+ 1.0 2.0) 3.0)"),
+        ("a ? b : c ? d : e", "(? a b (? c d e))"),
+        ("a ? b ? c : d : e", "(? a (? b c d) e)"),
+        ("x = a ? b : c", "(= x (? a b c))")
+    ];
+
+    for (input, expected_output) in cases {
+        let mut scanner = Scanner::new(String::from(input));
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let parser = Parser::new(tokens);
+        let ast = &parser.parse_ast().unwrap();
+
+        assert_eq!(format!("{}", ast), expected_output);
+    }
+}
+
+#[test]
+fn should_reject_conditional_without_colon() {
+    let input = "var a = true ? 1;";
+
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let result = parser.parse();
+
+    assert!(matches!(result, Err(ParsingError::ExpectedConditionalColon)));
+}
+
+#[test]
+fn should_reject_tokens_left_over_after_expression() {
+    let mut scanner = Scanner::new(String::from("1 + 2 3"));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+
+    assert!(matches!(parser.parse_ast(), Err(ParsingError::UnexpectedToken)));
+}
+
+#[test]
+fn should_format_single_expression_as_syntax_tree() {
+    let mut scanner = Scanner::new(String::from("1 + 2"));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    assert_eq!(format_syntax_tree(&Parser::new(tokens)).unwrap(), "(+ 1.0 2.0)");
+}
+
+#[test]
+fn should_format_program_as_one_statement_per_line() {
+    let mut scanner = Scanner::new(String::from("var a = 1;\nprint a;"));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    assert_eq!(format_syntax_tree(&Parser::new(tokens)).unwrap(), "(var a 1.0)\n(print a)");
+}
+
+#[test]
+fn should_report_expression_error_when_input_is_neither_expression_nor_program() {
+    let mut scanner = Scanner::new(String::from("1 +"));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    assert!(matches!(format_syntax_tree(&Parser::new(tokens)), Err(ParsingError::ExpectedExpression)));
+}
+
+#[test]
+fn should_parse_prefix_decrement_of_non_assignable_operand_as_double_negation() {
+    let cases = [
+        ("--(3)", "(- (- (group 3.0)))"),
+        ("--2 ** 2", "(- (- (** 2.0 2.0)))"),
+        ("---a", "(- (- (- a)))"),
+        ("--a", "(pre-- a)")
+    ];
+
+    for (input, expected_output) in cases {
+        let mut scanner = Scanner::new(String::from(input));
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let parser = Parser::new(tokens);
+        let ast = &parser.parse_ast().unwrap();
+
+        assert_eq!(format!("{}", ast), expected_output);
+    }
+}
+
+#[test]
+fn should_parse_decrement_between_operands_as_subtraction_of_negation() {
+    let cases = [
+        ("1--1", "(- 1.0 (- 1.0))"),
+        ("a--b", "(- a (- b))"),
+        ("a--b * c", "(- a (* (- b) c))"),
+        ("a-- - b", "(- (post-- a) b)"),
+        ("a--", "(post-- a)")
+    ];
+
+    for (input, expected_output) in cases {
+        let mut scanner = Scanner::new(String::from(input));
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let parser = Parser::new(tokens);
+        let ast = &parser.parse_ast().unwrap();
+
+        assert_eq!(format!("{}", ast), expected_output);
+    }
+}
@@ -0,0 +1,410 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use exitcode::ExitCode;
+use crate::lexing::token::Token;
+use crate::parsing::expression::Expression;
+use crate::parsing::statement::{FunctionDeclaration, Statement};
+use crate::util::error_handling::ExitCodeProvider;
+
+//** RESOLVING ERRORS **************************************************************************************************
+
+#[derive(Debug, Clone)]
+pub enum ResolvingError {
+    LocalReadInInitializer { line: u16, name: String },
+    DuplicateDeclaration { line: u16, name: String },
+    TopLevelReturn { line: u16 },
+    ReturnValueFromInitializer { line: u16 },
+    SelfInheritance { line: u16, name: String },
+    SuperOutsideClass { line: u16 },
+    SuperWithoutSuperclass { line: u16 },
+    ThisOutsideClass { line: u16 }
+}
+
+impl fmt::Display for ResolvingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResolvingError::LocalReadInInitializer { line, name } =>
+                write!(f, "[line {}] Error at '{}': Can't read local variable in its own initializer.", line, name),
+            ResolvingError::DuplicateDeclaration { line, name } =>
+                write!(f, "[line {}] Error at '{}': Already a variable with this name in this scope.", line, name),
+            ResolvingError::TopLevelReturn { line } =>
+                write!(f, "[line {}] Error at 'return': Can't return from top-level code.", line),
+            ResolvingError::ReturnValueFromInitializer { line } =>
+                write!(f, "[line {}] Error at 'return': Can't return a value from an initializer.", line),
+            ResolvingError::SelfInheritance { line, name } =>
+                write!(f, "[line {}] Error at '{}': A class can't inherit from itself.", line, name),
+            ResolvingError::SuperOutsideClass { line } =>
+                write!(f, "[line {}] Error at 'super': Can't use 'super' outside of a class.", line),
+            ResolvingError::SuperWithoutSuperclass { line } =>
+                write!(f, "[line {}] Error at 'super': Can't use 'super' in a class with no superclass.", line),
+            ResolvingError::ThisOutsideClass { line } =>
+                write!(f, "[line {}] Error at 'this': Can't use 'this' outside of a class.", line)
+        }
+    }
+}
+
+impl ExitCodeProvider for ResolvingError {
+    fn get_output(&self) -> Option<String> {
+        None
+    }
+
+    fn get_error_details(&self) -> Option<String> {
+        Some(format!("{}", self))
+    }
+
+    fn get_exit_code(&self) -> ExitCode {
+        match self {
+            ResolvingError::LocalReadInInitializer { .. } => exitcode::DATAERR,
+            ResolvingError::DuplicateDeclaration { .. } => exitcode::DATAERR,
+            ResolvingError::TopLevelReturn { .. } => exitcode::DATAERR,
+            ResolvingError::ReturnValueFromInitializer { .. } => exitcode::DATAERR,
+            ResolvingError::SelfInheritance { .. } => exitcode::DATAERR,
+            ResolvingError::SuperOutsideClass { .. } => exitcode::DATAERR,
+            ResolvingError::SuperWithoutSuperclass { .. } => exitcode::DATAERR,
+            ResolvingError::ThisOutsideClass { .. } => exitcode::DATAERR
+        }
+    }
+}
+
+//** RESOLVER **********************************************************************************************************
+
+#[derive(Clone, Copy, PartialEq)]
+enum FunctionType {
+    None,
+    Function,
+    Initializer,
+    Method
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ClassType {
+    None,
+    Class,
+    Subclass
+}
+
+/// Walks the parsed program once before it is run.  Every time a variable is referenced, the
+/// resolver records how many scopes separate the reference from the declaration.  Because that
+/// is decided by where the code is written rather than by what has run so far, a closure always
+/// sees the variable it saw when it was declared, even if a later declaration in an enclosing
+/// block reuses the name.
+///
+/// Each scope maps a variable name to whether its initializer has finished resolving.  Global
+/// variables are not tracked at all.
+pub struct Resolver {
+    scopes: Vec<HashMap<String, bool>>,
+    current_function: FunctionType,
+    current_class: ClassType
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Resolver {
+            scopes: Vec::new(),
+            current_function: FunctionType::None,
+            current_class: ClassType::None
+        }
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, name: &Token) -> Result<(), ResolvingError> {
+        if let Some(scope) = self.scopes.last_mut() {
+            if scope.contains_key(&name.get_name()) {
+                return Err(ResolvingError::DuplicateDeclaration { line: name.line, name: name.get_name() });
+            }
+
+            scope.insert(name.get_name(), false);
+        }
+
+        Ok(())
+    }
+
+    fn define(&mut self, name: &Token) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.get_name(), true);
+        }
+    }
+
+    fn define_keyword(&mut self, keyword: &str) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(keyword.to_string(), true);
+        }
+    }
+
+    /// Methods are resolved inside a scope that only holds `this`, which matches the environment
+    /// a method gets when it is bound to an instance.  A subclass adds one more scope outside of
+    /// that for `super`.
+    fn resolve_class(
+        &mut self,
+        name: &Token,
+        superclass: &Option<Expression>,
+        methods: &[Rc<FunctionDeclaration>]) -> Result<(), ResolvingError>
+    {
+        if let Some(superclass) = superclass {
+            if let Expression::Variable { name: superclass_name, depth: _ } = superclass {
+                if superclass_name.get_name() == name.get_name() {
+                    return Err(ResolvingError::SelfInheritance { line: superclass_name.line, name: superclass_name.get_name() });
+                }
+            }
+
+            self.current_class = ClassType::Subclass;
+            self.resolve_expression(superclass)?;
+
+            self.begin_scope();
+            self.define_keyword("super");
+        }
+
+        self.begin_scope();
+        self.define_keyword("this");
+
+        let result = self.resolve_methods(methods);
+
+        self.end_scope();
+
+        if superclass.is_some() {
+            self.end_scope();
+        }
+
+        result
+    }
+
+    fn resolve_methods(&mut self, methods: &[Rc<FunctionDeclaration>]) -> Result<(), ResolvingError> {
+        for method in methods {
+            let function_type = if method.name.get_name() == "init" {
+                FunctionType::Initializer
+            } else {
+                FunctionType::Method
+            };
+
+            self.resolve_function(method, function_type)?;
+        }
+
+        Ok(())
+    }
+
+    fn resolve_local(&self, name: &Token, depth: &Cell<Option<usize>>) {
+        let position = self.scopes
+            .iter()
+            .rev()
+            .position(|scope| scope.contains_key(&name.get_name()));
+
+        depth.set(position);
+    }
+
+    fn resolve_function(&mut self, declaration: &FunctionDeclaration, function_type: FunctionType) -> Result<(), ResolvingError> {
+        let enclosing_function = self.current_function;
+        self.current_function = function_type;
+        self.begin_scope();
+
+        let result = self.resolve_function_body(declaration);
+
+        self.end_scope();
+        self.current_function = enclosing_function;
+        result
+    }
+
+    fn resolve_function_body(&mut self, declaration: &FunctionDeclaration) -> Result<(), ResolvingError> {
+        for param in &declaration.params {
+            self.declare(param)?;
+            self.define(param);
+        }
+
+        self.resolve(&declaration.body)
+    }
+
+    fn resolve_expression(&mut self, expression: &Expression) -> Result<(), ResolvingError> {
+        match expression {
+            Expression::Binary { left, operator: _, right } |
+            Expression::Logical { left, operator: _, right } => {
+                self.resolve_expression(left)?;
+                self.resolve_expression(right)
+            },
+            Expression::Unary { operator: _, right } => self.resolve_expression(right),
+            Expression::Grouping { expression } => self.resolve_expression(expression),
+            Expression::StringLiteral { .. } |
+            Expression::NumericLiteral { .. } |
+            Expression::BooleanLiteral { .. } |
+            Expression::NilLiteral => Ok(()),
+            Expression::Variable { name, depth } => {
+                let scope = self.scopes.last();
+
+                if let Some(false) = scope.and_then(|scope| scope.get(&name.get_name())) {
+                    return Err(ResolvingError::LocalReadInInitializer { line: name.line, name: name.get_name() });
+                }
+
+                self.resolve_local(name, depth);
+                Ok(())
+            },
+            Expression::Assign { name, value, depth } => {
+                self.resolve_expression(value)?;
+                self.resolve_local(name, depth);
+                Ok(())
+            },
+            Expression::CompoundAssign { target, operator: _, value } => {
+                self.resolve_expression(target)?;
+                self.resolve_expression(value)
+            },
+            Expression::Update { target, operator: _, prefix: _ } => self.resolve_expression(target),
+            Expression::Conditional { condition, then_branch, else_branch } => {
+                self.resolve_expression(condition)?;
+                self.resolve_expression(then_branch)?;
+                self.resolve_expression(else_branch)
+            },
+            Expression::Call { callee, paren: _, arguments } => {
+                self.resolve_expression(callee)?;
+
+                for argument in arguments {
+                    self.resolve_expression(argument)?;
+                }
+
+                Ok(())
+            },
+            Expression::Get { object, name: _ } => self.resolve_expression(object),
+            Expression::Interpolation { parts: elements } |
+            Expression::List { elements } => {
+                for element in elements {
+                    self.resolve_expression(element)?;
+                }
+
+                Ok(())
+            },
+            Expression::Map { brace: _, entries } => {
+                for (key, value) in entries {
+                    self.resolve_expression(key)?;
+                    self.resolve_expression(value)?;
+                }
+
+                Ok(())
+            },
+            Expression::Index { object, bracket: _, index } => {
+                self.resolve_expression(object)?;
+                self.resolve_expression(index)
+            },
+            Expression::IndexSet { object, bracket: _, index, value } => {
+                self.resolve_expression(object)?;
+                self.resolve_expression(index)?;
+                self.resolve_expression(value)
+            },
+            Expression::Slice { object, bracket: _, start, end } => {
+                self.resolve_expression(object)?;
+                self.resolve_expression(start)?;
+                self.resolve_expression(end)
+            },
+            Expression::Set { object, name: _, value } => {
+                self.resolve_expression(value)?;
+                self.resolve_expression(object)
+            },
+            Expression::Super { keyword, method: _, depth } => {
+                match self.current_class {
+                    ClassType::None => Err(ResolvingError::SuperOutsideClass { line: keyword.line }),
+                    ClassType::Class => Err(ResolvingError::SuperWithoutSuperclass { line: keyword.line }),
+                    ClassType::Subclass => {
+                        self.resolve_local(keyword, depth);
+                        Ok(())
+                    }
+                }
+            },
+            Expression::This { keyword, depth } => {
+                if self.current_class == ClassType::None {
+                    return Err(ResolvingError::ThisOutsideClass { line: keyword.line });
+                }
+
+                self.resolve_local(keyword, depth);
+                Ok(())
+            }
+        }
+    }
+
+    fn resolve_statement(&mut self, statement: &Statement) -> Result<(), ResolvingError> {
+        match statement {
+            Statement::PrintStmt { expression } |
+            Statement::ExpressionStmt { expression } => self.resolve_expression(expression),
+            Statement::VarStmt { name, initializer } => {
+                self.declare(name)?;
+
+                if let Some(initializer) = initializer {
+                    self.resolve_expression(initializer)?;
+                }
+
+                self.define(name);
+                Ok(())
+            },
+            Statement::BlockStmt { statements } => {
+                self.begin_scope();
+                let result = self.resolve(statements);
+                self.end_scope();
+
+                result
+            },
+            Statement::IfStmt { condition, then_branch, else_branch } => {
+                self.resolve_expression(condition)?;
+                self.resolve_statement(then_branch)?;
+
+                match else_branch {
+                    Some(else_branch) => self.resolve_statement(else_branch),
+                    None => Ok(())
+                }
+            },
+            Statement::WhileStmt { condition, body, increment, label: _ } => {
+                self.resolve_expression(condition)?;
+                self.resolve_statement(body)?;
+
+                match increment {
+                    Some(increment) => self.resolve_expression(increment),
+                    None => Ok(())
+                }
+            },
+            Statement::BreakStmt { .. } | Statement::ContinueStmt { .. } => Ok(()),
+            Statement::FunctionStmt { declaration } => {
+                // The name is defined before the body is resolved so the function can refer to
+                // itself recursively.
+                self.declare(&declaration.name)?;
+                self.define(&declaration.name);
+
+                self.resolve_function(declaration, FunctionType::Function)
+            },
+            Statement::ReturnStmt { keyword, value } => {
+                if self.current_function == FunctionType::None {
+                    return Err(ResolvingError::TopLevelReturn { line: keyword.line });
+                }
+
+                match value {
+                    Some(_) if self.current_function == FunctionType::Initializer => {
+                        Err(ResolvingError::ReturnValueFromInitializer { line: keyword.line })
+                    },
+                    Some(value) => self.resolve_expression(value),
+                    None => Ok(())
+                }
+            },
+            Statement::ClassStmt { name, superclass, methods } => {
+                self.declare(name)?;
+                self.define(name);
+
+                let enclosing_class = self.current_class;
+                self.current_class = ClassType::Class;
+
+                let result = self.resolve_class(name, superclass, methods);
+
+                self.current_class = enclosing_class;
+                result
+            }
+        }
+    }
+
+    pub fn resolve(&mut self, statements: &[Statement]) -> Result<(), ResolvingError> {
+        for statement in statements {
+            self.resolve_statement(statement)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use crate::lexing::scanner::Scanner;
+use crate::parsing::parser::Parser;
+use crate::resolving::resolver::{Resolver, ResolvingError};
+
+#[test]
+fn should_resolve_valid_program() {
+    let input = r##"
+        var a = 1;
+        fun add(b) { var c = a + b; return c; }
+        { var d = add(2); }
+        "##;
+
+    assert!(resolve(input).is_ok());
+}
+
+#[test]
+fn should_reject_reading_local_variable_in_its_own_initializer() {
+    let result = resolve("{ var a = 1; { var a = a; } }");
+
+    assert!(matches!(result, Err(ResolvingError::LocalReadInInitializer { line: 1, .. })));
+}
+
+#[test]
+fn should_allow_global_variable_in_its_own_initializer() {
+    assert!(resolve("var a = 1; var a = a;").is_ok());
+}
+
+#[test]
+fn should_reject_redeclaring_local_variable_in_same_scope() {
+    let result = resolve("fun f(a) { var a; }");
+
+    assert!(matches!(result, Err(ResolvingError::DuplicateDeclaration { .. })));
+}
+
+#[test]
+fn should_reject_return_from_top_level_code() {
+    let result = resolve("\nreturn 1;");
+
+    assert!(matches!(result, Err(ResolvingError::TopLevelReturn { line: 2 })));
+}
+
+#[test]
+fn should_reject_this_outside_of_class() {
+    let result = resolve("fun f() { return this; }");
+
+    assert!(matches!(result, Err(ResolvingError::ThisOutsideClass { .. })));
+}
+
+#[test]
+fn should_allow_this_inside_method() {
+    assert!(resolve("class Foo { bar() { return this; } }").is_ok());
+}
+
+#[test]
+fn should_reject_returning_value_from_initializer() {
+    let result = resolve("class Foo { init() { return 1; } }");
+
+    assert!(matches!(result, Err(ResolvingError::ReturnValueFromInitializer { .. })));
+}
+
+#[test]
+fn should_allow_empty_return_from_initializer() {
+    assert!(resolve("class Foo { init() { return; } }").is_ok());
+}
+
+#[test]
+fn should_reject_class_inheriting_from_itself() {
+    let error = resolve("class Oops < Oops {}").unwrap_err();
+
+    assert_eq!(format!("{}", error), "[line 1] Error at 'Oops': A class can't inherit from itself.");
+}
+
+#[test]
+fn should_reject_super_outside_of_class() {
+    let result = resolve("fun f() { super.method(); }");
+
+    assert!(matches!(result, Err(ResolvingError::SuperOutsideClass { .. })));
+}
+
+#[test]
+fn should_reject_super_in_class_without_superclass() {
+    let result = resolve("class Base { method() { super.method(); } }");
+
+    assert!(matches!(result, Err(ResolvingError::SuperWithoutSuperclass { .. })));
+}
+
+#[test]
+fn should_format_error_with_line_and_lexeme() {
+    let error = resolve("{ var a = a; }").unwrap_err();
+
+    assert_eq!(
+        format!("{}", error),
+        "[line 1] Error at 'a': Can't read local variable in its own initializer.");
+}
+
+fn resolve(input: &str) -> Result<(), ResolvingError> {
+    let mut scanner = Scanner::new(String::from(input));
+    let tokens = scanner.scan_tokens().unwrap();
+
+    let parser = Parser::new(tokens);
+    let statements = parser.parse().unwrap();
+
+    let mut resolver = Resolver::new();
+    resolver.resolve(&statements)
+}
@@ -1,7 +1,9 @@
 use exitcode::ExitCode;
+use crate::conformance::runner::ConformanceError;
 use crate::interpreting::evaluator::EvaluationError;
 use crate::lexing::scanner::ScanningErrorSummary;
 use crate::parsing::parser::ParsingError;
+use crate::resolving::resolver::ResolvingError;
 use crate::ValidationError;
 
 pub trait ExitCodeProvider {
@@ -45,8 +47,20 @@ impl From<ParsingError> for InterpreterError {
     }
 }
 
+impl From<ResolvingError> for InterpreterError {
+    fn from(value: ResolvingError) -> Self {
+        InterpreterError::new(value.get_output(), value.get_error_details(), value.get_exit_code())
+    }
+}
+
 impl From<EvaluationError> for InterpreterError {
     fn from(value: EvaluationError) -> Self {
         InterpreterError::new(value.get_output(), value.get_error_details(), value.get_exit_code())
     }
-}
\ No newline at end of file
+}
+
+impl From<ConformanceError> for InterpreterError {
+    fn from(value: ConformanceError) -> Self {
+        InterpreterError::new(value.get_output(), value.get_error_details(), value.get_exit_code())
+    }
+}
@@ -0,0 +1,79 @@
+use std::process::{Command, Output};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+fn run_interpreter(command: &str, path: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_codecrafters-interpreter"))
+        .arg(command)
+        .arg(format!("{}/{}", FIXTURES, path))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn should_print_only_program_output_when_running() {
+    let output = run_interpreter("run", "run/print.lox");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "first\n2\n");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn should_print_nothing_when_program_has_no_output() {
+    let output = run_interpreter("run", "run/silent.lox");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn should_report_stack_overflow_as_runtime_error() {
+    let output = run_interpreter("run", "run/stack_overflow.lox");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "100\n");
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "Stack overflow.\n[line 3]\n");
+    assert_eq!(output.status.code(), Some(70));
+}
+
+#[test]
+fn should_still_print_empty_result_of_other_commands() {
+    let output = run_interpreter("evaluate", "evaluate/empty_string.lox");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "\n");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn should_pass_conformance_suite_in_nested_directories() {
+    let output = run_interpreter("test", "conformance/passing");
+    let report = String::from_utf8_lossy(&output.stdout);
+
+    let passed: Vec<&str> = report.lines().filter(|line| line.starts_with("PASS ")).collect();
+
+    assert_eq!(passed.len(), 2);
+    assert!(passed[0].ends_with("nested/runtime_error.lox"));
+    assert!(passed[1].ends_with("print.lox"));
+    assert!(report.ends_with("Passed 2 of 2 tests.\n"));
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn should_fail_conformance_suite_when_runtime_error_line_differs() {
+    let output = run_interpreter("test", "conformance/failing");
+    let report = String::from_utf8_lossy(&output.stdout);
+
+    assert!(report.starts_with("FAIL "));
+    assert!(report.contains("Expected runtime error on '[line 4]' but got '[line 3]'."));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "1 of 1 tests failed.\n");
+    assert_eq!(output.status.code(), Some(70));
+}
+
+#[test]
+fn should_report_argument_range_and_usage_when_argument_count_is_wrong() {
+    let output = Command::new(env!("CARGO_BIN_EXE_codecrafters-interpreter")).arg("run").output().unwrap();
+    let errors = String::from_utf8_lossy(&output.stderr);
+
+    assert!(errors.starts_with("Expected 3 or 4 arguments, but received 2.\n"));
+    assert!(errors.contains("[--comments=slashes|hash]"));
+    assert_eq!(output.status.code(), Some(64));
+}